@@ -0,0 +1,75 @@
+use soroban_sdk::{
+    bytesn,
+    crypto::bls12_381::{G1Affine, G2Affine},
+    vec, Bytes, BytesN, Env,
+};
+
+/// Domain separation tag of drand's `bls-unchained-g1-rfc9380` scheme (quicknet)
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Round that is current at `timestamp`, 0 before genesis
+pub fn round_at(genesis_time: u64, period: u64, timestamp: u64) -> u64 {
+    if timestamp < genesis_time {
+        return 0;
+    }
+    (timestamp - genesis_time) / period + 1
+}
+
+/// Message signed for a round: sha256(round as u64 BE)
+pub fn round_message(env: &Env, round: u64) -> Bytes {
+    let digest = env.crypto().sha256(&Bytes::from_array(env, &round.to_be_bytes()));
+    Bytes::from_array(env, &digest.to_array())
+}
+
+/// Check a beacon signature (uncompressed G1) against the group key (uncompressed G2):
+/// e(sig, g2) == e(H(m), pk)
+pub fn verify(env: &Env, public_key: &BytesN<192>, round: u64, signature: &BytesN<96>) -> bool {
+    let bls = env.crypto().bls12_381();
+    let signature = G1Affine::from_bytes(signature.clone());
+    if !bls.g1_is_in_subgroup(&signature) {
+        return false;
+    }
+
+    let hashed = bls.hash_to_g1(&round_message(env, round), &Bytes::from_slice(env, DST));
+    let generator = G2Affine::from_bytes(g2_generator(env));
+    let public_key = G2Affine::from_bytes(public_key.clone());
+
+    bls.pairing_check(vec![env, -signature, hashed], vec![env, generator, public_key])
+}
+
+/// drand's published randomness for a beacon: sha256(compressed signature)
+pub fn randomness(env: &Env, signature: &BytesN<96>) -> BytesN<32> {
+    let compressed = compress_g1(env, &signature.to_array());
+    env.crypto().sha256(&Bytes::from_array(env, &compressed)).into()
+}
+
+/// Compressed (zcash) encoding of an uncompressed G1 point: x with the compression flag,
+/// plus the sort flag when y is the larger of y and p - y
+fn compress_g1(env: &Env, point: &[u8; 96]) -> [u8; 48] {
+    let mut x = [0u8; 48];
+    x.copy_from_slice(&point[..48]);
+    let mut y = [0u8; 48];
+    y.copy_from_slice(&point[48..]);
+
+    x[0] |= 0x80;
+    if y > p_minus_one_half(env).to_array() {
+        x[0] |= 0x20;
+    }
+    x
+}
+
+/// (p - 1) / 2 for the BLS12-381 base field, big-endian
+fn p_minus_one_half(env: &Env) -> BytesN<48> {
+    bytesn!(
+        env,
+        0x0d0088f51cbff34d258dd3db21a5d66bb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd555
+    )
+}
+
+/// BLS12-381 G2 generator, uncompressed
+fn g2_generator(env: &Env) -> BytesN<192> {
+    bytesn!(
+        env,
+        0x13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801
+    )
+}
@@ -0,0 +1,16 @@
+#[soroban_sdk::contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // Storage Errors
+    BeaconNotFound = 401,
+    RequestNotFound = 402,
+
+    // Request Errors
+    AlreadyFulfilled = 500,
+    RoundMismatch = 501,
+    InvalidSignature = 502,
+
+    // Config Errors
+    InvalidBeacon = 900,
+}
@@ -0,0 +1,54 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+#[contractevent(topics = ["drand_vrf_v1", "random_requested"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RandomRequested {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub requester: Address,
+    pub seed: BytesN<32>,
+    pub drand_round: u64,
+}
+
+#[contractevent(topics = ["drand_vrf_v1", "random_fulfilled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RandomFulfilled {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub requester: Address,
+    pub drand_round: u64,
+    pub randomness: BytesN<32>,
+    pub random_value: BytesN<32>,
+}
+
+/// Emitted when a contract requests randomness, relayers fulfill it once `drand_round` is out
+pub fn emit_random_requested(env: &Env, request_id: u64, requester: &Address, seed: &BytesN<32>, drand_round: u64) {
+    RandomRequested {
+        request_id,
+        requester: requester.clone(),
+        seed: seed.clone(),
+        drand_round,
+    }
+    .publish(env);
+}
+
+/// Emitted when a verified drand beacon is delivered for a request
+pub fn emit_random_fulfilled(
+    env: &Env,
+    request_id: u64,
+    requester: &Address,
+    drand_round: u64,
+    randomness: &BytesN<32>,
+    random_value: &BytesN<32>,
+) {
+    RandomFulfilled {
+        request_id,
+        requester: requester.clone(),
+        drand_round,
+        randomness: randomness.clone(),
+        random_value: random_value.clone(),
+    }
+    .publish(env);
+}
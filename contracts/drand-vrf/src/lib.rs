@@ -0,0 +1,320 @@
+#![no_std]
+
+//! Randomness from the drand beacon, verified on-chain with the BLS12-381 host functions.
+//!
+//! Each request is bound at `request_random` time to a drand round that hasn't been
+//! published yet. Once drand publishes it, anyone can relay the round's signature to
+//! `fulfill`; the contract checks it against the stored group key and calls back to the
+//! requester. Nobody, including the relayer, can choose the output.
+//!
+//! The contract targets drand's `bls-unchained-g1-rfc9380` scheme (quicknet): signatures
+//! on G1, group key on G2, message `sha256(round)`. The host only accepts uncompressed
+//! points, so relayers decompress the signature drand publishes before submitting it.
+
+use randomness::{RandomnessConsumerClient, RandomnessProvider};
+use soroban_sdk::{crypto::bls12_381::G2Affine, panic_with_error, Address, Bytes, BytesN, Env, contract, contractimpl};
+
+mod drand;
+mod error;
+mod events;
+mod storage;
+mod types;
+
+use error::Error;
+use types::{Beacon, RandomRequest, RequestStatus};
+
+#[contract]
+pub struct DrandVRF;
+
+#[contractimpl]
+impl DrandVRF {
+    /// The beacon can't be changed afterwards, so no one can swap the key under pending requests
+    pub fn __constructor(env: &Env, beacon: Beacon) -> Result<(), Error> {
+        if beacon.period == 0 || beacon.round_delay == 0 {
+            return Err(Error::InvalidBeacon);
+        }
+        let public_key = G2Affine::from_bytes(beacon.public_key.clone());
+        if !env.crypto().bls12_381().g2_is_in_subgroup(&public_key) {
+            return Err(Error::InvalidBeacon);
+        }
+
+        storage::set_beacon(env, &beacon);
+        Ok(())
+    }
+
+    /// Fulfill a pending request with the drand signature for its round (anyone can relay).
+    /// The requester sees `keeper` as the account that delivered it.
+    pub fn fulfill(env: Env, request_id: u64, round: u64, signature: BytesN<96>, keeper: Address) -> Result<(), Error> {
+        let mut request = storage::get_request(&env, request_id).ok_or(Error::RequestNotFound)?;
+        if request.status != RequestStatus::PENDING {
+            return Err(Error::AlreadyFulfilled);
+        }
+        if round != request.drand_round {
+            return Err(Error::RoundMismatch);
+        }
+
+        let randomness = Self::verify_beacon(env.clone(), round, signature)?;
+
+        // Mix in the seed so requests sharing a round get different values
+        let mut preimage = Bytes::from_array(&env, &randomness.to_array());
+        preimage.append(&Bytes::from_array(&env, &request.seed.to_array()));
+        let random_value: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        // Mark fulfilled before calling out so the request can't be replayed
+        request.status = RequestStatus::FULFILLED;
+        storage::set_request(&env, &request);
+
+        events::emit_random_fulfilled(&env, request_id, &request.requester, round, &randomness, &random_value);
+
+        RandomnessConsumerClient::new(&env, &request.requester).fulfill_random(
+            &env.current_contract_address(),
+            &request_id,
+            &random_value,
+            &keeper,
+        );
+
+        storage::extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Verify a drand beacon and return its randomness, as published by drand
+    pub fn verify_beacon(env: Env, round: u64, signature: BytesN<96>) -> Result<BytesN<32>, Error> {
+        let beacon = storage::get_beacon(&env)?;
+        if !drand::verify(&env, &beacon.public_key, round, &signature) {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(drand::randomness(&env, &signature))
+    }
+
+    /// Get a recorded request
+    pub fn get_request(env: Env, request_id: u64) -> Option<RandomRequest> {
+        storage::get_request(&env, request_id)
+    }
+
+    /// Get the beacon this contract verifies against
+    pub fn get_beacon(env: Env) -> Result<Beacon, Error> {
+        storage::get_beacon(&env)
+    }
+
+    /// drand round that is current at this ledger's timestamp
+    pub fn current_round(env: Env) -> Result<u64, Error> {
+        let beacon = storage::get_beacon(&env)?;
+        Ok(drand::round_at(beacon.genesis_time, beacon.period, env.ledger().timestamp()))
+    }
+}
+
+#[contractimpl]
+impl RandomnessProvider for DrandVRF {
+    /// Request random number - binds it to a future drand round and returns its ID
+    /// A relayer calls fulfill() with that round's signature once drand publishes it
+    fn request_random(env: Env, requester: Address, seed: BytesN<32>) -> u64 {
+        // Only the requester itself can open a request on its behalf
+        requester.require_auth();
+
+        let beacon = storage::get_beacon(&env).unwrap_or_else(|error| panic_with_error!(&env, error));
+        let current = drand::round_at(beacon.genesis_time, beacon.period, env.ledger().timestamp());
+
+        let request = RandomRequest {
+            id: storage::next_request_id(&env),
+            requester: requester.clone(),
+            seed: seed.clone(),
+            drand_round: current + beacon.round_delay,
+            status: RequestStatus::PENDING,
+        };
+        storage::set_request(&env, &request);
+
+        events::emit_random_requested(&env, request.id, &requester, &seed, request.drand_round);
+
+        storage::extend_instance_ttl(&env);
+        request.id
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hex_literal::hex;
+    use soroban_sdk::{Env, symbol_short, testutils::{Address as _, Ledger}};
+
+    // drand quicknet (chain 52db9ba7...e971), group key decompressed from the published
+    // 83cf0f28...ece45a
+    const QUICKNET_PUBLIC_KEY: [u8; 192] = hex!("03cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a01a714f2edb74119a2f2b0d5a7c75ba902d163700a61bc224ededd8e63aef7be1aaf8e93d7a9718b047ccddb3eb5d68b0e5db2b6bfbb01c867749cadffca88b36c24f3012ba09fc4d3022c5c37dce0f977d3adb5d183c7477c442b1f04515273");
+    const QUICKNET_GENESIS: u64 = 1692803367;
+    const QUICKNET_PERIOD: u64 = 3;
+
+    // Published quicknet round 1000: signature b44679b9...63ed5e39 decompressed, and the
+    // beacon's randomness sha256 of the compressed signature
+    const ROUND_1000_SIGNATURE: [u8; 96] = hex!("144679b9a59af2ec876b1a6b1ad52ea9b1615fc3982b19576350f93447cb1125e342b73a8dd2bacbe47e4b6b63ed5e3911f92e4521ef54f047b64b85fa98db2d46f0f44add1f60b93f8a0dbddd63b34f238657c2d93aed18b90bddd60a01b6d2");
+    const ROUND_1000_RANDOMNESS: [u8; 32] = hex!("fe290beca10872ef2fb164d2aa4442de4566183ec51c56ff3cd603d930e54fdd");
+    // Round 1000's signature with y negated: still a valid G1 point, but not drand's signature
+    const TAMPERED_SIGNATURE: [u8; 96] = hex!("144679b9a59af2ec876b1a6b1ad52ea9b1615fc3982b19576350f93447cb1125e342b73a8dd2bacbe47e4b6b63ed5e390807e3a5179091aa03655c3048b2d1aa1d86573a1665b20627a6c4e3194d42d4fb25a83bd81912e700f32229f5fdf3d9");
+
+    // Mock raffle contract for testing VRF callbacks
+    #[contract]
+    pub struct MockRaffleContract;
+
+    #[contractimpl]
+    impl MockRaffleContract {
+        pub fn fulfill_random(env: Env, _vrf: Address, request_id: u64, randomness: BytesN<32>, keeper: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("random"), &(request_id, randomness));
+            env.storage().instance().set(&symbol_short!("keeper"), &keeper);
+        }
+
+        pub fn get_stored_random(env: Env) -> Option<(u64, BytesN<32>)> {
+            env.storage().instance().get(&symbol_short!("random"))
+        }
+
+        pub fn get_keeper(env: Env) -> Option<Address> {
+            env.storage().instance().get(&symbol_short!("keeper"))
+        }
+    }
+
+    fn beacon(env: &Env, public_key: &[u8; 192]) -> Beacon {
+        Beacon {
+            public_key: BytesN::from_array(env, public_key),
+            genesis_time: QUICKNET_GENESIS,
+            period: QUICKNET_PERIOD,
+            round_delay: 1,
+        }
+    }
+
+    fn setup<'a>(env: &Env) -> DrandVRFClient<'a> {
+        env.cost_estimate().budget().reset_unlimited();
+        let vrf_id = env.register(DrandVRF, (beacon(env, &QUICKNET_PUBLIC_KEY),));
+        DrandVRFClient::new(env, &vrf_id)
+    }
+
+    /// Move the ledger to the moment drand round `round` is current
+    fn set_round(env: &Env, round: u64) {
+        env.ledger()
+            .set_timestamp(QUICKNET_GENESIS + (round - 1) * QUICKNET_PERIOD);
+    }
+
+    fn seed(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[7u8; 32])
+    }
+
+    #[test]
+    fn test_accepts_quicknet_key() {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let vrf_id = env.register(DrandVRF, (beacon(&env, &QUICKNET_PUBLIC_KEY),));
+        let client = DrandVRFClient::new(&env, &vrf_id);
+
+        assert_eq!(client.get_beacon().public_key.to_array(), QUICKNET_PUBLIC_KEY);
+
+        set_round(&env, 1);
+        assert_eq!(client.current_round(), 1);
+        env.ledger().set_timestamp(QUICKNET_GENESIS + 3 * QUICKNET_PERIOD - 1);
+        assert_eq!(client.current_round(), 3);
+        env.ledger().set_timestamp(QUICKNET_GENESIS - 1);
+        assert_eq!(client.current_round(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rejects_zero_period() {
+        let env = Env::default();
+        let mut config = beacon(&env, &QUICKNET_PUBLIC_KEY);
+        config.period = 0;
+        env.register(DrandVRF, (config,));
+    }
+
+    #[test]
+    fn test_request_binds_future_round() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+
+        set_round(&env, 999);
+        let request_id = client.request_random(&raffle_id, &seed(&env));
+
+        let request = client.get_request(&request_id).unwrap();
+        assert_eq!(request.drand_round, 1000);
+        assert_eq!(request.requester, raffle_id);
+        assert_eq!(request.status, RequestStatus::PENDING);
+    }
+
+    #[test]
+    fn test_verify_beacon_matches_drand_randomness() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        // Checked against the real quicknet key, DST and hash-to-curve
+        let randomness = client.verify_beacon(&1000, &BytesN::from_array(&env, &ROUND_1000_SIGNATURE));
+        assert_eq!(randomness.to_array(), ROUND_1000_RANDOMNESS);
+
+        assert_eq!(
+            client.try_verify_beacon(&1000, &BytesN::from_array(&env, &TAMPERED_SIGNATURE)),
+            Err(Ok(Error::InvalidSignature))
+        );
+
+        // A signature only verifies for its own round
+        assert_eq!(
+            client.try_verify_beacon(&1001, &BytesN::from_array(&env, &ROUND_1000_SIGNATURE)),
+            Err(Ok(Error::InvalidSignature))
+        );
+    }
+
+    #[test]
+    fn test_fulfill_with_beacon() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+        let raffle_client = MockRaffleContractClient::new(&env, &raffle_id);
+
+        set_round(&env, 999);
+        let request_id = client.request_random(&raffle_id, &seed(&env));
+
+        // Anyone can relay the beacon and is passed on as the keeper
+        let keeper = Address::generate(&env);
+        env.set_auths(&[]);
+        let signature = BytesN::from_array(&env, &ROUND_1000_SIGNATURE);
+        client.fulfill(&request_id, &1000, &signature, &keeper);
+
+        let mut preimage = Bytes::from_array(&env, &ROUND_1000_RANDOMNESS);
+        preimage.append(&Bytes::from_array(&env, &seed(&env).to_array()));
+        let expected: BytesN<32> = env.crypto().sha256(&preimage).into();
+        assert_eq!(raffle_client.get_stored_random(), Some((request_id, expected)));
+        assert_eq!(raffle_client.get_keeper(), Some(keeper.clone()));
+        assert_eq!(client.get_request(&request_id).unwrap().status, RequestStatus::FULFILLED);
+
+        assert_eq!(
+            client.try_fulfill(&request_id, &1000, &signature, &keeper),
+            Err(Ok(Error::AlreadyFulfilled))
+        );
+    }
+
+    #[test]
+    fn test_fulfill_rejects_other_rounds() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+
+        set_round(&env, 999);
+        let request_id = client.request_random(&raffle_id, &seed(&env));
+
+        // Only the round bound at request time is accepted
+        let keeper = Address::generate(&env);
+        let published = BytesN::from_array(&env, &ROUND_1000_SIGNATURE);
+        assert_eq!(
+            client.try_fulfill(&request_id, &1002, &published, &keeper),
+            Err(Ok(Error::RoundMismatch))
+        );
+
+        // Nor can a signature drand didn't publish be passed off as the round's
+        let tampered = BytesN::from_array(&env, &TAMPERED_SIGNATURE);
+        assert_eq!(
+            client.try_fulfill(&request_id, &1000, &tampered, &keeper),
+            Err(Ok(Error::InvalidSignature))
+        );
+        assert_eq!(client.try_fulfill(&99, &1000, &published, &keeper), Err(Ok(Error::RequestNotFound)));
+
+        assert_eq!(client.get_request(&request_id).unwrap().status, RequestStatus::PENDING);
+    }
+}
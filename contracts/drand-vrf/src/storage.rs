@@ -0,0 +1,51 @@
+use soroban_sdk::Env;
+
+use crate::{error::Error, types::{Beacon, RandomRequest, Storage}};
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Beacon
+pub fn set_beacon(env: &Env, beacon: &Beacon) {
+    env.storage().instance().set(&Storage::Beacon, beacon);
+}
+
+pub fn get_beacon(env: &Env) -> Result<Beacon, Error> {
+    env.storage().instance().get(&Storage::Beacon).ok_or(Error::BeaconNotFound)
+}
+
+// Requests
+pub fn next_request_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&Storage::NextRequestId).unwrap_or(1);
+    env.storage().instance().set(&Storage::NextRequestId, &(id + 1));
+    id
+}
+
+pub fn get_request(env: &Env, id: u64) -> Option<RandomRequest> {
+    let key = Storage::Request(id);
+    let request = env.storage().persistent().get(&key);
+    if request.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    request
+}
+
+pub fn set_request(env: &Env, request: &RandomRequest) {
+    let key = Storage::Request(request.id);
+    env.storage().persistent().set(&key, request);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
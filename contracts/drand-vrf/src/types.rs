@@ -0,0 +1,36 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Beacon {
+    pub public_key: BytesN<192>, // drand group key, uncompressed G2 point
+    pub genesis_time: u64,       // Unix time of round 1
+    pub period: u64,             // Seconds between rounds
+    pub round_delay: u64,        // Rounds past the current one a request is bound to
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum RequestStatus {
+    PENDING,    // Waiting for the drand round to be published
+    FULFILLED,  // Randomness delivered to the requester
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RandomRequest {
+    pub id: u64,
+    pub requester: Address,
+    pub seed: BytesN<32>,
+    pub drand_round: u64,      // Future drand round whose signature fulfills the request
+    pub status: RequestStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Storage {
+    Beacon,
+    NextRequestId,
+    Request(u64),     // request id -> RandomRequest
+}
@@ -0,0 +1,80 @@
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+use crate::types::Contribution;
+
+/// Draw index of the round's winning ticket, later draws are free for extra prizes
+pub const WINNER_DRAW: u32 = 0;
+
+/// Seed committed to when a draw is requested: sha256(contract xdr || round || total tickets || ledger)
+pub fn draw_seed(env: &Env, round: u32, total_tickets: u32) -> BytesN<32> {
+    let mut preimage = env.current_contract_address().to_xdr(env);
+    preimage.extend_from_array(&round.to_be_bytes());
+    preimage.extend_from_array(&total_tickets.to_be_bytes());
+    preimage.extend_from_array(&env.ledger().sequence().to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Commitment a participant submits on entry: sha256(participant xdr || round || secret)
+pub fn reveal_commitment(env: &Env, round: u32, participant: &Address, secret: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = participant.clone().to_xdr(env);
+    preimage.extend_from_array(&round.to_be_bytes());
+    preimage.extend_from_array(&secret.to_array());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Fold a revealed secret into the accumulator. XOR keeps the result independent of reveal order.
+pub fn accumulate_secret(env: &Env, accumulator: &BytesN<32>, secret: &BytesN<32>) -> BytesN<32> {
+    let mut folded = accumulator.to_array();
+    for (byte, secret_byte) in folded.iter_mut().zip(secret.to_array()) {
+        *byte ^= secret_byte;
+    }
+    BytesN::from_array(env, &folded)
+}
+
+/// Seed of a commit-reveal draw: sha256(contract xdr || round || revealed secrets || reveal deadline).
+/// Fixed once the reveals are in, whichever ledger the draw is finalized on.
+pub fn reveal_seed(env: &Env, round: u32, accumulator: &BytesN<32>, deadline_ledger: u32) -> BytesN<32> {
+    let mut preimage = env.current_contract_address().to_xdr(env);
+    preimage.extend_from_array(&round.to_be_bytes());
+    preimage.extend_from_array(&accumulator.to_array());
+    preimage.extend_from_array(&deadline_ledger.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Seed of a VRF draw: sha256 over (provider xdr || randomness) of every answered
+/// provider, in provider order. Answers are public once stored, so the provider completing the
+/// threshold can pick its value knowing the outcome: it is trusted as much as a single provider.
+pub fn contributions_seed(env: &Env, contributions: &Vec<Contribution>) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    for contribution in contributions.iter() {
+        if let Some(randomness) = contribution.random_value {
+            preimage.append(&contribution.provider.to_xdr(env));
+            preimage.extend_from_array(&randomness.to_array());
+        }
+    }
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Uniform index in `0..bound` for independent draw `draw` of a round's randomness.
+/// Candidates are sha256(randomness || draw || counter) read as a big-endian u64, and any
+/// past the largest multiple of `bound` are rejected so no index is favored.
+pub fn draw_index(env: &Env, randomness: &BytesN<32>, draw: u32, bound: u32) -> u32 {
+    let bound = bound as u64;
+    let limit = u64::MAX - u64::MAX % bound;
+
+    let mut counter: u32 = 0;
+    loop {
+        let mut preimage = Bytes::from_array(env, &randomness.to_array());
+        preimage.extend_from_array(&draw.to_be_bytes());
+        preimage.extend_from_array(&counter.to_be_bytes());
+        let digest = env.crypto().sha256(&preimage).to_array();
+
+        let mut candidate_bytes = [0u8; 8];
+        candidate_bytes.copy_from_slice(&digest[..8]);
+        let candidate = u64::from_be_bytes(candidate_bytes);
+        if candidate < limit {
+            return (candidate % bound) as u32;
+        }
+        counter += 1;
+    }
+}
@@ -8,23 +8,53 @@ pub enum Error {
     RoundNotFound = 403,
     RoundStatsNotFound = 404,
     WinnerNotFound = 405,
+    DrawRecordNotFound = 406,
 
     // State Errors
     RoundNotOpen = 500,
     InvalidState = 501,
     TargetNotMet = 502,
+    DrawNotTimedOut = 503,
+    DrawAttemptsExhausted = 504,
+    DrawAttemptsRemaining = 505,
+    RoundPruned = 506,
+    NotEnoughParticipants = 507,
+    SoldOut = 508,
+    SalesClosed = 509,
+    SalesOpen = 510,
+    CloseWindowOpen = 511,
 
     // Prize/Winner Errors
     AlreadyClaimed = 600,
     NotWinner = 601,
     InsufficientTickets = 602,
+    NothingToRefund = 603,
+    AlreadyRefunded = 604,
 
     // VRF Errors
     UnauthorizedVRF = 700,
     VRFRequestFailed = 701,
+    StaleVRFRequest = 702,
+    DuplicateContribution = 703,
 
     // Transfer Errors
     FailedToTransferToWinner = 800,
     FailedToTransferFromUser = 801,
     NoBalanceToTransfer = 802,
+    InvalidAmount = 803,
+    InsufficientSurplus = 804,
+    MaxCostExceeded = 805,
+
+    // Config Errors
+    InvalidConfig = 900,
+    ConfigLocked = 901,
+
+    // Commit-Reveal Errors
+    WrongDrawMode = 1000,
+    NoCommitment = 1001,
+    InvalidReveal = 1002,
+    AlreadyRevealed = 1003,
+    RevealWindowClosed = 1004,
+    RevealWindowOpen = 1005,
+    NoReveals = 1006,
 }
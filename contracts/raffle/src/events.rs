@@ -1,94 +1,468 @@
-use soroban_sdk::{contractevent, Address, Env};
-
-#[contractevent]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PlayerEntered {
-    #[topic]
-    pub round: u32,
-    #[topic]
-    pub player: Address,
-    pub num_tickets: u32,
-    pub total_tickets: u32,
-}
-
-#[contractevent]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ReadyToDraw {
-    #[topic]
-    pub round: u32,
-    pub total_participants: u32,
-}
-
-#[contractevent]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct DrawRequested {
-    #[topic]
-    pub round: u32,
-    pub vrf_request_id: u64,
-}
-
-#[contractevent]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct WinnerSelected {
-    #[topic]
-    pub round: u32,
-    #[topic]
-    pub winner: Address,
-    pub prize_amount: i128,
-}
-
-#[contractevent]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct RoundStarted {
-    #[topic]
-    pub round: u32,
-}
-
-#[contractevent]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PrizeClaimed {
-    #[topic]
-    pub round: u32,
-    #[topic]
-    pub winner: Address,
-    pub amount: i128,
-}
-
-/// Emitted when a player enters the raffle
-pub fn emit_player_entered(env: &Env, round: u32, player: &Address, num_tickets: u32, total_tickets: u32) {
+//! Raffle event schema.
+//!
+//! Every event is published with the topics `["raffle_v1", <event_name>, ...indexed fields]`.
+//! The first topic carries the schema version so indexers can filter on it and keep
+//! decoding old events after a breaking change bumps it.
+
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+use crate::types::{Config, KeeperTask, TicketEntry};
+
+/// Version carried in the first topic of every raffle event
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Declare a raffle event published under `[EVENT_TOPIC_PREFIX, name, ...]`
+macro_rules! raffle_event {
+    ($($args:tt)*) => {
+        raffle_event_with_prefix! { "raffle_v1", $($args)* }
+    };
+}
+
+macro_rules! raffle_event_with_prefix {
+    ($prefix:tt, prefix) => {
+        $prefix
+    };
+    ($prefix:tt, $name:tt, $item:item) => {
+        #[contractevent(topics = [$prefix, $name])]
+        $item
+    };
+}
+
+/// First topic of every raffle event, `raffle_v<EVENT_SCHEMA_VERSION>`
+pub const EVENT_TOPIC_PREFIX: &str = raffle_event!(prefix);
+
+// `contractevent` only takes literal topics, so the prefix is written once, in `raffle_event!`,
+// and the build fails if it no longer matches EVENT_SCHEMA_VERSION
+const _: () = assert!(prefix_has_version(EVENT_TOPIC_PREFIX.as_bytes(), EVENT_SCHEMA_VERSION));
+
+/// Whether `prefix` is `raffle_v` followed by `version` in decimal
+const fn prefix_has_version(prefix: &[u8], version: u32) -> bool {
+    let base = b"raffle_v";
+    if prefix.len() <= base.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < base.len() {
+        if prefix[i] != base[i] {
+            return false;
+        }
+        i += 1;
+    }
+    let mut parsed: u32 = 0;
+    while i < prefix.len() {
+        if !prefix[i].is_ascii_digit() {
+            return false;
+        }
+        parsed = parsed * 10 + (prefix[i] - b'0') as u32;
+        i += 1;
+    }
+    parsed == version
+}
+
+// ========== ROUND LIFECYCLE ==========
+
+raffle_event! {
+    "player_entered",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct PlayerEntered {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub player: Address,
+        pub num_tickets: u32,
+        pub amount_paid: i128,
+        pub total_tickets: u32,
+        pub entry_index: u32,  // Leaf of the purchase in the round's participants tree
+        pub marginal_price: i128, // Price of the round's next ticket after this entry
+    }
+}
+
+raffle_event! {
+    "ready_to_draw",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ReadyToDraw {
+        #[topic]
+        pub round: u32,
+        pub total_tickets: u32,
+    }
+}
+
+raffle_event! {
+    "sold_out",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct SoldOut {
+        #[topic]
+        pub round: u32,
+        pub total_tickets: u32,
+    }
+}
+
+raffle_event! {
+    "sales_closed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct SalesClosed {
+        #[topic]
+        pub round: u32,
+        pub total_tickets: u32,
+        pub draw_ledger: u32,  // First ledger the draw can be requested on
+    }
+}
+
+raffle_event! {
+    "auto_draw_failed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AutoDrawFailed {
+        #[topic]
+        pub round: u32,
+    }
+}
+
+raffle_event! {
+    "draw_requested",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct DrawRequested {
+        #[topic]
+        pub round: u32,
+        pub vrf_request_id: u64,
+        pub total_tickets: u32,
+        pub seed: BytesN<32>,
+    }
+}
+
+raffle_event! {
+    "keeper_paid",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct KeeperPaid {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub keeper: Address,
+        pub task: KeeperTask,
+        pub amount: i128,
+        pub prize_pool: i128,  // Pool left after the bounty
+    }
+}
+
+raffle_event! {
+    "draw_retried",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct DrawRetried {
+        #[topic]
+        pub round: u32,
+        pub attempt: u32,
+        pub stale_request_id: u64,
+        pub vrf_request_id: u64,
+        pub seed: BytesN<32>,
+    }
+}
+
+raffle_event! {
+    "contribution_received",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ContributionReceived {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub provider: Address,
+        pub request_id: u64,
+        pub answered: u32,
+        pub threshold: u32,
+    }
+}
+
+raffle_event! {
+    "winner_selected",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct WinnerSelected {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub winner: Address,
+        pub prize_amount: i128,
+        pub winning_ticket: u32,
+        pub randomness: BytesN<32>,
+        pub total_tickets: u32,
+    }
+}
+
+raffle_event! {
+    "round_started",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RoundStarted {
+        #[topic]
+        pub round: u32,
+    }
+}
+
+raffle_event! {
+    "prize_claimed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct PrizeClaimed {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub winner: Address,
+        pub amount: i128,
+    }
+}
+
+raffle_event! {
+    "round_pruned",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RoundPruned {
+        #[topic]
+        pub round: u32,
+        pub pruned: u32,
+        pub remaining: u32,
+    }
+}
+
+raffle_event! {
+    "reveal_started",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RevealStarted {
+        #[topic]
+        pub round: u32,
+        pub commitments: u32,
+        pub deadline_ledger: u32,
+    }
+}
+
+raffle_event! {
+    "secret_revealed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct SecretRevealed {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub participant: Address,
+        pub secret: BytesN<32>,
+    }
+}
+
+raffle_event! {
+    "reveal_finalized",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RevealFinalized {
+        #[topic]
+        pub round: u32,
+        pub commitments: u32,
+        pub reveals: u32,
+        pub seed: BytesN<32>,
+    }
+}
+
+raffle_event! {
+    "refunds_enabled",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RefundsEnabled {
+        #[topic]
+        pub round: u32,
+        pub draw_attempts: u32,
+        pub refund_pool: i128,
+    }
+}
+
+raffle_event! {
+    "refund_claimed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct RefundClaimed {
+        #[topic]
+        pub round: u32,
+        #[topic]
+        pub player: Address,
+        pub tickets: u32,
+        pub amount: i128,
+    }
+}
+
+// ========== ADMIN ACTIONS ==========
+
+raffle_event! {
+    "admin_changed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AdminChanged {
+        #[topic]
+        pub previous_admin: Address,
+        #[topic]
+        pub new_admin: Address,
+    }
+}
+
+raffle_event! {
+    "treasurer_changed",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct TreasurerChanged {
+        #[topic]
+        pub treasurer: Address,
+    }
+}
+
+raffle_event! {
+    "contract_upgraded",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct ContractUpgraded {
+        pub new_wasm_hash: BytesN<32>,
+    }
+}
+
+raffle_event! {
+    "config_updated",
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct ConfigUpdated {
+        pub config: Config,
+    }
+}
+
+raffle_event! {
+    "season_started",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct SeasonStarted {
+        #[topic]
+        pub season: u32,
+    }
+}
+
+raffle_event! {
+    "tokens_recovered",
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct TokensRecovered {
+        #[topic]
+        pub token: Address,
+        #[topic]
+        pub to: Address,
+        pub amount: i128,
+    }
+}
+
+/// Emitted when a player enters the raffle, for the purchase recorded at `entry_index`
+pub fn emit_player_entered(
+    env: &Env,
+    round: u32,
+    entry: &TicketEntry,
+    entry_index: u32,
+    amount_paid: i128,
+    marginal_price: i128,
+) {
     PlayerEntered {
         round,
-        player: player.clone(),
-        num_tickets,
-        total_tickets,
+        player: entry.participant.clone(),
+        num_tickets: entry.tickets,
+        amount_paid,
+        total_tickets: entry.first_ticket + entry.tickets,
+        entry_index,
+        marginal_price,
     }
     .publish(env);
 }
 
-/// Emitted when target participants reached and ready to draw
-pub fn emit_ready_to_draw(env: &Env, round: u32, total_participants: u32) {
+/// Emitted once per round, by the entry that reaches the target tickets
+pub fn emit_ready_to_draw(env: &Env, round: u32, total_tickets: u32) {
     ReadyToDraw {
         round,
-        total_participants,
+        total_tickets,
+    }
+    .publish(env);
+}
+
+/// Emitted by the entry that sells the last ticket under max_total_tickets
+pub fn emit_sold_out(env: &Env, round: u32, total_tickets: u32) {
+    SoldOut { round, total_tickets }.publish(env);
+}
+
+/// Emitted when a round stops selling tickets and enters its close window
+pub fn emit_sales_closed(env: &Env, round: u32, total_tickets: u32, draw_ledger: u32) {
+    SalesClosed {
+        round,
+        total_tickets,
+        draw_ledger,
     }
     .publish(env);
 }
 
+/// Emitted when the automatic draw could not be requested, the round stays open
+pub fn emit_auto_draw_failed(env: &Env, round: u32) {
+    AutoDrawFailed { round }.publish(env);
+}
+
 /// Emitted when draw is requested
-pub fn emit_draw_requested(env: &Env, round: u32, vrf_request_id: u64) {
+pub fn emit_draw_requested(env: &Env, round: u32, vrf_request_id: u64, total_tickets: u32, seed: &BytesN<32>) {
     DrawRequested {
         round,
         vrf_request_id,
+        total_tickets,
+        seed: seed.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when a keeper bounty is paid out of a round's pool
+pub fn emit_keeper_paid(env: &Env, round: u32, keeper: &Address, task: KeeperTask, amount: i128, prize_pool: i128) {
+    KeeperPaid {
+        round,
+        keeper: keeper.clone(),
+        task,
+        amount,
+        prize_pool,
+    }
+    .publish(env);
+}
+
+/// Emitted when a timed-out draw is re-requested from the VRF
+pub fn emit_draw_retried(
+    env: &Env,
+    round: u32,
+    attempt: u32,
+    stale_request_id: u64,
+    vrf_request_id: u64,
+    seed: &BytesN<32>,
+) {
+    DrawRetried {
+        round,
+        attempt,
+        stale_request_id,
+        vrf_request_id,
+        seed: seed.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when a randomness provider answers a draw
+pub fn emit_contribution_received(
+    env: &Env,
+    round: u32,
+    provider: &Address,
+    request_id: u64,
+    answered: u32,
+    threshold: u32,
+) {
+    ContributionReceived {
+        round,
+        provider: provider.clone(),
+        request_id,
+        answered,
+        threshold,
     }
     .publish(env);
 }
 
 /// Emitted when winner is selected
-pub fn emit_winner_selected(env: &Env, round: u32, winner: &Address, prize_amount: i128) {
+pub fn emit_winner_selected(
+    env: &Env,
+    round: u32,
+    winner: &Address,
+    prize_amount: i128,
+    winning_ticket: u32,
+    randomness: &BytesN<32>,
+    total_tickets: u32,
+) {
     WinnerSelected {
         round,
         winner: winner.clone(),
         prize_amount,
+        winning_ticket,
+        randomness: randomness.clone(),
+        total_tickets,
     }
     .publish(env);
 }
@@ -107,3 +481,113 @@ pub fn emit_prize_claimed(env: &Env, round: u32, winner: &Address, amount: i128)
     }
     .publish(env);
 }
+
+/// Emitted when per-participant data of a finalized round is pruned
+pub fn emit_round_pruned(env: &Env, round: u32, pruned: u32, remaining: u32) {
+    RoundPruned {
+        round,
+        pruned,
+        remaining,
+    }
+    .publish(env);
+}
+
+/// Emitted when sales close on a commit-reveal round and the reveal window opens
+pub fn emit_reveal_started(env: &Env, round: u32, commitments: u32, deadline_ledger: u32) {
+    RevealStarted {
+        round,
+        commitments,
+        deadline_ledger,
+    }
+    .publish(env);
+}
+
+/// Emitted when a participant reveals the secret behind their commitment
+pub fn emit_secret_revealed(env: &Env, round: u32, participant: &Address, secret: &BytesN<32>) {
+    SecretRevealed {
+        round,
+        participant: participant.clone(),
+        secret: secret.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the reveal window is closed and the draw seed fixed
+pub fn emit_reveal_finalized(env: &Env, round: u32, commitments: u32, reveals: u32, seed: &BytesN<32>) {
+    RevealFinalized {
+        round,
+        commitments,
+        reveals,
+        seed: seed.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin abandons a draw the VRF never answered
+pub fn emit_refunds_enabled(env: &Env, round: u32, draw_attempts: u32, refund_pool: i128) {
+    RefundsEnabled {
+        round,
+        draw_attempts,
+        refund_pool,
+    }
+    .publish(env);
+}
+
+/// Emitted when a player reclaims the tickets of a refunded round
+pub fn emit_refund_claimed(env: &Env, round: u32, player: &Address, tickets: u32, amount: i128) {
+    RefundClaimed {
+        round,
+        player: player.clone(),
+        tickets,
+        amount,
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin hands over the admin role
+pub fn emit_admin_changed(env: &Env, previous_admin: &Address, new_admin: &Address) {
+    AdminChanged {
+        previous_admin: previous_admin.clone(),
+        new_admin: new_admin.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin sets a new treasurer
+pub fn emit_treasurer_changed(env: &Env, treasurer: &Address) {
+    TreasurerChanged {
+        treasurer: treasurer.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the contract wasm is upgraded
+pub fn emit_contract_upgraded(env: &Env, new_wasm_hash: &BytesN<32>) {
+    ContractUpgraded {
+        new_wasm_hash: new_wasm_hash.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin replaces the raffle configuration
+pub fn emit_config_updated(env: &Env, config: &Config) {
+    ConfigUpdated {
+        config: config.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin starts a new leaderboard season
+pub fn emit_season_started(env: &Env, season: u32) {
+    SeasonStarted { season }.publish(env);
+}
+
+/// Emitted when the treasurer recovers surplus or foreign tokens
+pub fn emit_tokens_recovered(env: &Env, token: &Address, to: &Address, amount: i128) {
+    TokensRecovered {
+        token: token.clone(),
+        to: to.clone(),
+        amount,
+    }
+    .publish(env);
+}
@@ -2,51 +2,143 @@
 extern crate std;
 
 use super::*;
+use types::{PriceCurve, PriceTier};
 use soroban_sdk::{
-    testutils::Address as _,
+    testutils::{storage::Persistent as _, Address as _, Ledger},
     token::{Client as TokenClient, StellarAssetClient as StellarAssetAdmin},
-    Address, Env,
+    Address, Env, Symbol, TryFromVal, Val,
 };
 
 // Mock VRF for integration tests - does NOT callback immediately to avoid re-entrance
 mod mock_vrf {
-    use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, Symbol, Val, Vec};
+    use randomness::{RandomnessConsumerClient, RandomnessProvider};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env};
 
     #[contract]
     pub struct MockVRF;
 
     #[contractimpl]
-    impl MockVRF {
+    impl RandomnessProvider for MockVRF {
         /// Simulates VRF request_random WITHOUT immediate callback
-        /// Returns request ID without calling back
-        pub fn request_random(env: Env, _requester: Address) -> u64 {
-            // Generate request ID using env.prng()
-            env.prng().gen()
+        /// Returns a sequential request ID without calling back
+        fn request_random(env: Env, requester: Address, _seed: BytesN<32>) -> u64 {
+            requester.require_auth();
+            let request_id: u64 = env.storage().instance().get(&symbol_short!("next_id")).unwrap_or(1);
+            env.storage().instance().set(&symbol_short!("next_id"), &(request_id + 1));
+            env.storage().instance().set(&(symbol_short!("pending"), requester), &request_id);
+            request_id
         }
+    }
 
-        /// Fulfill a random number request by calling back to the requester
+    #[contractimpl]
+    impl MockVRF {
+        /// Fulfill the requester's latest request by calling back to the requester
         /// This simulates what a Node.js oracle would do in production
-        pub fn fulfill(env: Env, requester: Address, random_value: u64) {
-            let callback_args: Vec<Val> = (
-                env.current_contract_address(),
-                random_value,
-            )
-                .into_val(&env);
-
-            let _: Val = env.invoke_contract(
-                &requester,
-                &Symbol::new(&env, "fulfill_random"),
-                callback_args,
+        pub fn fulfill(env: Env, requester: Address, randomness: BytesN<32>) {
+            let keeper = env.current_contract_address();
+            Self::fulfill_by(env, requester, keeper, randomness);
+        }
+
+        /// Fulfill the requester's latest request, reporting `keeper` as the one who triggered it
+        pub fn fulfill_by(env: Env, requester: Address, keeper: Address, randomness: BytesN<32>) {
+            let request_id: u64 = env
+                .storage()
+                .instance()
+                .get(&(symbol_short!("pending"), requester.clone()))
+                .unwrap();
+            Self::deliver(&env, &requester, request_id, &randomness, &keeper);
+        }
+
+        /// Call back to the requester with an explicit request ID
+        pub fn fulfill_request(env: Env, requester: Address, request_id: u64, randomness: BytesN<32>) {
+            Self::deliver(&env, &requester, request_id, &randomness, &env.current_contract_address());
+        }
+
+        fn deliver(env: &Env, requester: &Address, request_id: u64, randomness: &BytesN<32>, keeper: &Address) {
+            RandomnessConsumerClient::new(env, requester).fulfill_random(
+                &env.current_contract_address(),
+                &request_id,
+                randomness,
+                keeper,
             );
         }
 
-        /// Get a random number (utility for testing)
-        pub fn get_random(env: Env) -> u64 {
+        /// Get 32 random bytes (utility for testing)
+        pub fn get_random(env: Env) -> BytesN<32> {
             env.prng().gen()
         }
     }
 }
 
+// Synchronous provider for integration tests - answers in the same call with a preset value
+mod sync_provider {
+    use randomness::SyncRandomnessProvider;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env};
+
+    #[contract]
+    pub struct SyncProvider;
+
+    #[contractimpl]
+    impl SyncRandomnessProvider for SyncProvider {
+        fn random_value(env: Env, _requester: Address, _seed: BytesN<32>) -> BytesN<32> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("value"))
+                .unwrap_or(BytesN::from_array(&env, &[0u8; 32]))
+        }
+    }
+
+    #[contractimpl]
+    impl SyncProvider {
+        /// Set the randomness returned to the next requester
+        pub fn set_value(env: Env, value: BytesN<32>) {
+            env.storage().instance().set(&symbol_short!("value"), &value);
+        }
+    }
+}
+
+const DAY_IN_LEDGERS: u32 = 17280;
+
+// Advance the ledger sequence by the given number of days
+fn advance_days(env: &Env, days: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += days * DAY_IN_LEDGERS);
+}
+
+// Fixed randomness, for tests that don't care which ticket wins
+fn fixed_randomness(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+// Randomness whose winning draw lands on `ticket` out of `total_tickets`
+fn randomness_for_ticket(env: &Env, ticket: u32, total_tickets: u32) -> BytesN<32> {
+    (0..=u8::MAX)
+        .map(|byte| fixed_randomness(env, byte))
+        .find(|randomness| commitment::draw_index(env, randomness, commitment::WINNER_DRAW, total_tickets) == ticket)
+        .unwrap()
+}
+
+// Seed a draw settles with when `provider` is the only one to answer
+fn answer_seed(env: &Env, provider: &Address, answer: &BytesN<32>) -> BytesN<32> {
+    let contribution = Contribution { request_id: 0, provider: provider.clone(), random_value: Some(answer.clone()) };
+    commitment::contributions_seed(env, &Vec::from_array(env, [contribution]))
+}
+
+// Answer from `provider` whose seed lands the winning draw on `ticket` out of `total_tickets`
+fn answer_for_ticket(env: &Env, provider: &Address, ticket: u32, total_tickets: u32) -> BytesN<32> {
+    (0..=u8::MAX)
+        .map(|byte| fixed_randomness(env, byte))
+        .find(|answer| {
+            let seed = answer_seed(env, provider, answer);
+            commitment::draw_index(env, &seed, commitment::WINNER_DRAW, total_tickets) == ticket
+        })
+        .unwrap()
+}
+
+// Remaining TTL of a persistent raffle entry
+fn persistent_ttl(env: &Env, raffle_id: &Address, key: &types::Storage) -> u32 {
+    env.as_contract(raffle_id, || env.storage().persistent().get_ttl(key))
+}
+
 // Helper function to create a test token
 fn create_token<'a>(env: &Env) -> (Address, TokenClient<'a>, StellarAssetAdmin<'a>) {
     let admin = Address::generate(env);
@@ -112,7 +204,7 @@ fn test_full_raffle_flow() {
     assert!(raffle_client.is_ready_to_draw());
 
     // Request draw - this will call VRF and transition to DRAWING state
-    let _request_id = raffle_client.request_draw();
+    let _request_id = raffle_client.request_draw(&Address::generate(&env));
 
     // Simulate VRF oracle: generate random number and fulfill (in production, a Node.js listener would do this)
     let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
@@ -160,7 +252,7 @@ fn test_claim_prize() {
     raffle_client.enter(&charlie, &1); // Charlie has 1 ticket
 
     // Request draw and manually fulfill
-    let _request_id = raffle_client.request_draw();
+    let _request_id = raffle_client.request_draw(&Address::generate(&env));
     let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
     let random_value = vrf_client.get_random();
     vrf_client.fulfill(&raffle_id, &random_value);
@@ -212,7 +304,7 @@ fn test_multiple_rounds() {
         raffle_client.enter(&charlie, &1);
 
         // Request draw and manually fulfill
-        let _request_id = raffle_client.request_draw();
+        let _request_id = raffle_client.request_draw(&Address::generate(&env));
         let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
         let random_value = vrf_client.get_random();
         vrf_client.fulfill(&raffle_id, &random_value);
@@ -249,14 +341,14 @@ fn test_claim_all_prizes() {
         raffle_client.enter(&alice, &5); // Alice buys max 5 tickets
         raffle_client.enter(&bob, &3);   // Bob buys 3 tickets
         raffle_client.enter(&charlie, &1); // Charlie buys 1 ticket
-        let _request_id = raffle_client.request_draw();
+        let _request_id = raffle_client.request_draw(&Address::generate(&env));
         let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
         let random_value = vrf_client.get_random();
         vrf_client.fulfill(&raffle_id, &random_value);
     }
 
     // Check how many rounds Alice won
-    let _alice_winning_rounds = raffle_client.get_user_winning_rounds(&alice);
+    let _alice_winning_rounds = raffle_client.get_user_winning_rounds(&alice, &0, &10);
 
     // Get Alice's unclaimed prizes
     let unclaimed_prizes = raffle_client.get_unclaimed_prizes(&alice);
@@ -265,7 +357,7 @@ fn test_claim_all_prizes() {
     if num_unclaimed > 0 {
         // Claim all prizes at once
         let balance_before = token_client.balance(&alice);
-        let total_claimed = raffle_client.claim_all_prizes(&alice);
+        let total_claimed = raffle_client.claim_all_prizes(&alice, &10);
         let balance_after = token_client.balance(&alice);
 
         assert_eq!(balance_after, balance_before + total_claimed);
@@ -296,7 +388,7 @@ fn test_winner_selection_fairness() {
     raffle_client.enter(&bob, &3);
     raffle_client.enter(&charlie, &1);
 
-    let _request_id = raffle_client.request_draw();
+    let _request_id = raffle_client.request_draw(&Address::generate(&env));
     let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
     let random_value = vrf_client.get_random();
     vrf_client.fulfill(&raffle_id, &random_value);
@@ -330,7 +422,7 @@ fn test_cannot_draw_before_target_met() {
     assert!(!raffle_client.is_ready_to_draw());
 
     // Try to request draw - should fail
-    let result = raffle_client.try_request_draw();
+    let result = raffle_client.try_request_draw(&Address::generate(&env));
     assert!(result.is_err());
 }
 
@@ -362,7 +454,7 @@ fn test_round_state_transitions() {
     assert_eq!(round_info.state, State::OPEN);
 
     // Request draw - state goes OPEN -> DRAWING
-    let _request_id = raffle_client.request_draw();
+    let _request_id = raffle_client.request_draw(&Address::generate(&env));
 
     // Verify state is DRAWING
     let round_info = raffle_client.get_round_info(&1);
@@ -398,3 +490,1951 @@ fn test_bucket_pattern_many_participants() {
     let participants = raffle_client.get_participants(&1);
     assert_eq!(participants.len(), 10);
 }
+
+#[test]
+fn test_winner_leaderboard_updates_on_draw() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    token_admin.mint(&alice, &1_000_000_000i128);
+    token_admin.mint(&bob, &1_000_000_000i128);
+    token_admin.mint(&charlie, &1_000_000_000i128);
+
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    for _ in 1..=2 {
+        raffle_client.enter(&alice, &5);
+        raffle_client.enter(&bob, &3);
+        raffle_client.enter(&charlie, &1);
+        raffle_client.request_draw(&Address::generate(&env));
+        vrf_client.fulfill(&raffle_id, &vrf_client.get_random());
+    }
+
+    let board = raffle_client.get_leaderboard(&LeaderboardKind::TopWinners, &None);
+    let total_won: i128 = board.iter().map(|entry| entry.total).sum();
+    assert_eq!(total_won, 18_000_000i128);
+
+    // Each winner appears once with their accumulated winnings
+    let winner1 = raffle_client.get_winner(&1).unwrap().winner;
+    let winner2 = raffle_client.get_winner(&2).unwrap().winner;
+    let expected_top = if winner1 == winner2 { 18_000_000i128 } else { 9_000_000i128 };
+    assert_eq!(board.get(0).unwrap().total, expected_top);
+    assert_eq!(board.len(), if winner1 == winner2 { 1 } else { 2 });
+
+    // Season board matches all-time during the first season
+    assert_eq!(raffle_client.get_leaderboard(&LeaderboardKind::TopWinners, &Some(1)), board);
+}
+
+#[test]
+fn test_audit_tracks_liabilities() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    let audit = raffle_client.audit();
+    assert_eq!(audit, Audit { balance: 0, liabilities: 0, surplus: 0 });
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    let audit = raffle_client.audit();
+    assert_eq!(audit.balance, 9_000_000i128);
+    assert_eq!(audit.liabilities, 9_000_000i128);
+    assert_eq!(audit.surplus, 0);
+
+    // Stray transfers show up as surplus
+    token_admin.mint(&raffle_id, &1_000i128);
+    assert_eq!(raffle_client.audit().surplus, 1_000i128);
+
+    // Drawing moves the pool into an unclaimed prize without changing the total
+    raffle_client.request_draw(&Address::generate(&env));
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &vrf_client.get_random());
+    assert_eq!(raffle_client.audit().liabilities, 9_000_000i128);
+
+    // Claiming releases the liability
+    let winner = raffle_client.get_winner(&1).unwrap().winner;
+    raffle_client.claim_prize(&winner, &1);
+    let audit = raffle_client.audit();
+    assert_eq!(audit, Audit { balance: 1_000i128, liabilities: 0, surplus: 1_000i128 });
+}
+
+/// Small deterministic xorshift generator so invariant runs are reproducible
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn randomness(&mut self, env: &Env) -> BytesN<32> {
+        let mut bytes = [0u8; 32];
+        for chunk in bytes.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next().to_be_bytes());
+        }
+        BytesN::from_array(env, &bytes)
+    }
+}
+
+#[test]
+fn test_solvency_invariant_random_sequences() {
+    for seed in 1..=8u64 {
+        let env = Env::default();
+        let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+        env.mock_all_auths();
+        let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+        let mut rng = XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+
+        let players: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+        for player in players.iter() {
+            token_admin.mint(player, &1_000_000_000i128);
+        }
+
+        // Shadow model of what the contract owes
+        let mut open_pool: i128 = 0;
+        let mut unclaimed: i128 = 0;
+
+        for _ in 0..40 {
+            let player = &players[rng.below(players.len() as u64) as usize];
+            match rng.below(4) {
+                0 | 1 => {
+                    let round = raffle_client.get_current_round_number();
+                    let before = raffle_client.get_round_stats(&round).prize_pool;
+                    raffle_client.enter(player, &(1 + rng.below(5) as u32));
+                    open_pool += raffle_client.get_round_stats(&round).prize_pool - before;
+                }
+                2 => {
+                    if raffle_client.is_ready_to_draw() {
+                        let round = raffle_client.get_current_round_number();
+                        raffle_client.request_draw(&Address::generate(&env));
+                        vrf_client.fulfill(&raffle_id, &rng.randomness(&env));
+                        let prize = raffle_client.get_winner(&round).unwrap().amount;
+                        open_pool -= prize;
+                        unclaimed += prize;
+                    }
+                }
+                _ => {
+                    let before = token_client.balance(player);
+                    let claimed = raffle_client.claim_all_prizes(player, &10);
+                    assert_eq!(token_client.balance(player), before + claimed);
+                    unclaimed -= claimed;
+                }
+            }
+
+            let audit = raffle_client.audit();
+            assert_eq!(audit.liabilities, open_pool + unclaimed);
+            assert!(audit.surplus >= 0);
+            assert_eq!(audit.balance, token_client.balance(&raffle_id));
+        }
+    }
+}
+
+#[test]
+fn test_recover_tokens_cannot_touch_unclaimed_prize() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, token_id, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    raffle_client.request_draw(&Address::generate(&env));
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &vrf_client.get_random());
+
+    // Next round has its own open pool
+    raffle_client.enter(&alice, &2);
+
+    let treasury = Address::generate(&env);
+    let result = raffle_client.try_recover_tokens(&token_id, &treasury, &1i128);
+    assert_eq!(result, Err(Ok(Error::InsufficientSurplus)));
+
+    // Winner can still claim the full prize
+    let winner = raffle_client.get_winner(&1).unwrap().winner;
+    assert_eq!(raffle_client.claim_prize(&winner, &1), 9_000_000i128);
+    assert_eq!(token_client.balance(&raffle_id), 2_000_000i128);
+
+    // Still nothing to recover: the balance is the open pool
+    let result = raffle_client.try_recover_tokens(&token_id, &treasury, &1i128);
+    assert_eq!(result, Err(Ok(Error::InsufficientSurplus)));
+}
+
+#[test]
+fn test_winner_record_ttl_extended_on_read() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    raffle_client.request_draw(&Address::generate(&env));
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &vrf_client.get_random());
+
+    let key = types::Storage::WinnerRecord(1);
+    assert_eq!(persistent_ttl(&env, &raffle_id, &key), storage::PERSISTENT_BUMP_AMOUNT);
+
+    // Past the lifetime threshold a read brings the TTL back to the full bump
+    advance_days(&env, 30);
+    assert!(persistent_ttl(&env, &raffle_id, &key) < storage::PERSISTENT_LIFETIME_THRESHOLD);
+    raffle_client.get_winner(&1).unwrap();
+    assert_eq!(persistent_ttl(&env, &raffle_id, &key), storage::PERSISTENT_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_claim_prize_after_winner_record_archived() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    raffle_client.request_draw(&Address::generate(&env));
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &vrf_client.get_random());
+
+    let winner = raffle_client.get_winner(&1).unwrap().winner;
+
+    // Winner comes back long after the record's TTL ran out
+    advance_days(&env, 150);
+
+    let balance_before = token_client.balance(&winner);
+    assert_eq!(raffle_client.claim_prize(&winner, &1), 9_000_000i128);
+    assert_eq!(token_client.balance(&winner), balance_before + 9_000_000i128);
+
+    // The restored record is kept alive for a full period again
+    let key = types::Storage::WinnerRecord(1);
+    assert_eq!(persistent_ttl(&env, &raffle_id, &key), storage::PERSISTENT_BUMP_AMOUNT);
+    assert!(raffle_client.get_winner(&1).unwrap().claimed);
+}
+
+#[test]
+fn test_bump_round_extends_participant_data() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &2);
+    raffle_client.enter(&bob, &1);
+
+    let keys = [
+        types::Storage::Round(1),
+        types::Storage::RoundStats(1),
+        types::Storage::ParticipantBucket(1, 0),
+        types::Storage::UserTickets(1, alice.clone()),
+        types::Storage::UserTickets(1, bob.clone()),
+    ];
+
+    // The round sits idle past the lifetime threshold
+    advance_days(&env, 25);
+    for key in keys.iter() {
+        assert!(persistent_ttl(&env, &raffle_id, key) < storage::PERSISTENT_LIFETIME_THRESHOLD);
+    }
+
+    // Anyone can keep it alive, 2 participants then 2 ticket entries
+    assert_eq!(raffle_client.bump_round(&1, &0, &3), 1);
+    assert_eq!(raffle_client.bump_round(&1, &3, &3), 0);
+    for key in keys.iter() {
+        assert_eq!(persistent_ttl(&env, &raffle_id, key), storage::PERSISTENT_BUMP_AMOUNT);
+    }
+
+    // Tickets survive well past the original expiry
+    advance_days(&env, 110);
+    assert_eq!(raffle_client.get_user_tickets(&1, &alice), 2);
+    assert_eq!(raffle_client.get_participants(&1).len(), 2);
+
+    // Unknown rounds are rejected
+    let result = raffle_client.try_bump_round(&99, &0, &10);
+    assert_eq!(result, Err(Ok(Error::RoundNotFound)));
+}
+
+#[test]
+fn test_refunds_survive_bumped_rounds() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_timeout = 10;
+    config.max_draw_attempts = 1;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    start_round_one_draw(&raffle_client, &alice, &bob);
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    raffle_client.enable_refunds();
+    raffle_client.claim_refund(&alice, &1);
+
+    // Keeping the round alive covers who was refunded as well as what they paid
+    advance_days(&env, 25);
+    assert_eq!(raffle_client.bump_round(&1, &0, &2), 2);
+    assert_eq!(raffle_client.bump_round(&1, &2, &2), 0);
+    for key in [
+        types::Storage::Refunded(1, alice.clone()),
+        types::Storage::UserPaid(1, bob.clone()),
+        types::Storage::TicketEntry(1, 1),
+    ] {
+        assert_eq!(persistent_ttl(&env, &raffle_id, &key), storage::PERSISTENT_BUMP_AMOUNT);
+    }
+
+    advance_days(&env, 110);
+    assert_eq!(raffle_client.try_claim_refund(&alice, &1), Err(Ok(Error::AlreadyRefunded)));
+    assert_eq!(raffle_client.claim_refund(&bob, &1), 4_000_000i128);
+    assert_eq!(token_client.balance(&bob), 100_000_000i128);
+}
+
+#[test]
+fn test_prune_round_in_chunks() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    // Enough participants to span two buckets
+    let mut players = std::vec::Vec::new();
+    for _ in 0..105 {
+        let user = Address::generate(&env);
+        token_admin.mint(&user, &100_000_000i128);
+        raffle_client.enter(&user, &1);
+        players.push(user);
+    }
+
+    raffle_client.request_draw(&Address::generate(&env));
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &vrf_client.get_random());
+    let winner_record = raffle_client.get_winner(&1).unwrap();
+
+    // The summary keeps the root frozen when the draw was requested
+    let expected_root = raffle_client.get_round_info(&1).participants_root.unwrap();
+
+    // 105 participants, then one ticket entry each
+    assert_eq!(raffle_client.prune_round(&1, &40), 170);
+    let summary = raffle_client.get_round_summary(&1).unwrap();
+    assert_eq!(summary.participant_count, 105);
+    assert_eq!(summary.participants_root, expected_root);
+    assert_eq!(summary.pruned_participants, 40);
+    assert_eq!((summary.entry_count, summary.pruned_entries), (105, 0));
+    assert_eq!(raffle_client.get_user_tickets(&1, &players[0]), 0);
+    assert_eq!(raffle_client.get_user_tickets(&1, &players[40]), 1);
+
+    assert_eq!(raffle_client.prune_round(&1, &40), 130);
+    assert_eq!(raffle_client.prune_round(&1, &40), 90);
+    assert_eq!(raffle_client.get_round_summary(&1).unwrap().pruned_entries, 15);
+    assert_eq!(raffle_client.prune_round(&1, &100), 0);
+
+    // Nothing left per participant, but the summary and winner survive
+    assert_eq!(raffle_client.get_participants(&1).len(), 0);
+    assert_eq!(raffle_client.get_user_tickets(&1, &players[104]), 0);
+    env.as_contract(&raffle_id, || {
+        assert!(!env.storage().persistent().has(&types::Storage::ParticipantBucket(1, 0)));
+        assert!(!env.storage().persistent().has(&types::Storage::ParticipantBucket(1, 1)));
+    });
+    assert!(raffle_client.get_ticket_entry(&1, &0).is_none());
+    assert!(raffle_client.get_ticket_entry(&1, &104).is_none());
+    assert_eq!(raffle_client.get_round_summary(&1).unwrap().participants_root, expected_root);
+    assert_eq!(raffle_client.get_winner(&1).unwrap(), winner_record);
+    assert_eq!(raffle_client.get_round_stats(&1).total_participants, 105);
+
+    // Further calls are no-ops
+    assert_eq!(raffle_client.prune_round(&1, &40), 0);
+}
+
+#[test]
+fn test_draw_events_carry_ticket_data() {
+    use soroban_sdk::{testutils::Events as _, xdr::ScVal, Event as _};
+
+    let env = Env::default();
+    let to_scval = |val: &Val| ScVal::try_from_val(&env, val).unwrap();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+
+    // DrawRequested carries the ticket total and the committed seed
+    let request_id = raffle_client.request_draw(&Address::generate(&env));
+    let (_, topics, data) = env.events().all().last().unwrap();
+    let seed = raffle_client.get_round_info(&1).draw_seed.unwrap();
+    let expected = events::DrawRequested {
+        round: 1,
+        vrf_request_id: request_id,
+        total_tickets: 9,
+        seed,
+    };
+    assert_eq!(topics, expected.topics(&env));
+    assert_eq!(to_scval(&data), to_scval(&expected.data(&env)));
+
+    // WinnerSelected carries the winning ticket, randomness and ticket total
+    let answer = answer_for_ticket(&env, &vrf_id, 3, 9);
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &answer);
+
+    // Ticket 3 falls in Alice's 0..5 range
+    let expected = events::WinnerSelected {
+        round: 1,
+        winner: alice.clone(),
+        prize_amount: 9_000_000i128,
+        winning_ticket: 3,
+        randomness: answer_seed(&env, &vrf_id, &answer),
+        total_tickets: 9,
+    };
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(contract, topics, _)| *contract == raffle_id && *topics == expected.topics(&env))
+        .unwrap();
+    assert_eq!(to_scval(&data), to_scval(&expected.data(&env)));
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, alice);
+}
+
+// Run a full round where Alice enters first and ticket 0 wins
+fn run_round_won_by_first_entrant(
+    env: &Env,
+    raffle_id: &Address,
+    raffle_client: &LuckyLedgersRaffleClient,
+    vrf_id: &Address,
+    alice: &Address,
+    others: &[Address],
+) {
+    raffle_client.enter(alice, &5);
+    raffle_client.enter(&others[0], &3);
+    raffle_client.enter(&others[1], &1);
+    raffle_client.request_draw(&Address::generate(env));
+    mock_vrf::MockVRFClient::new(env, vrf_id).fulfill(raffle_id, &answer_for_ticket(env, vrf_id, 0, 9));
+}
+
+#[test]
+fn test_claims_do_not_duplicate_winning_rounds() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let others = [Address::generate(&env), Address::generate(&env)];
+    token_admin.mint(&alice, &1_000_000_000i128);
+    for other in others.iter() {
+        token_admin.mint(other, &1_000_000_000i128);
+    }
+
+    for _ in 0..3 {
+        run_round_won_by_first_entrant(&env, &raffle_id, &raffle_client, &vrf_id, &alice, &others);
+    }
+
+    assert_eq!(raffle_client.get_unclaimed_prizes(&alice).len(), 3);
+
+    raffle_client.claim_prize(&alice, &2);
+    raffle_client.claim_all_prizes(&alice, &10);
+
+    // History lists each win exactly once, and nothing is left to claim
+    let winning_rounds = raffle_client.get_user_winning_rounds(&alice, &0, &10);
+    assert_eq!(winning_rounds, soroban_sdk::vec![&env, 1u32, 2, 3]);
+    assert_eq!(raffle_client.get_unclaimed_prizes(&alice).len(), 0);
+
+    let history = raffle_client.get_user_win_history(&alice, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert!(history.iter().all(|record| record.claimed));
+}
+
+#[test]
+fn test_claim_all_prizes_is_bounded() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let others = [Address::generate(&env), Address::generate(&env)];
+    token_admin.mint(&alice, &1_000_000_000i128);
+    for other in others.iter() {
+        token_admin.mint(other, &1_000_000_000i128);
+    }
+
+    for _ in 0..3 {
+        run_round_won_by_first_entrant(&env, &raffle_id, &raffle_client, &vrf_id, &alice, &others);
+    }
+
+    // Only the two oldest prizes are claimed
+    let balance_before = token_client.balance(&alice);
+    assert_eq!(raffle_client.claim_all_prizes(&alice, &2), 18_000_000i128);
+    assert_eq!(token_client.balance(&alice), balance_before + 18_000_000i128);
+
+    let unclaimed = raffle_client.get_unclaimed_prizes(&alice);
+    assert_eq!(unclaimed.len(), 1);
+    assert_eq!(unclaimed.get(0).unwrap().round, 3);
+
+    assert_eq!(raffle_client.claim_all_prizes(&alice, &2), 9_000_000i128);
+    assert_eq!(raffle_client.claim_all_prizes(&alice, &2), 0);
+}
+
+#[test]
+fn test_win_history_pagination() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let others = [Address::generate(&env), Address::generate(&env)];
+    token_admin.mint(&alice, &1_000_000_000i128);
+    for other in others.iter() {
+        token_admin.mint(other, &1_000_000_000i128);
+    }
+
+    for _ in 0..3 {
+        run_round_won_by_first_entrant(&env, &raffle_id, &raffle_client, &vrf_id, &alice, &others);
+    }
+
+    assert_eq!(raffle_client.get_user_winning_rounds(&alice, &0, &2), soroban_sdk::vec![&env, 1u32, 2]);
+    assert_eq!(raffle_client.get_user_winning_rounds(&alice, &2, &2), soroban_sdk::vec![&env, 3u32]);
+    assert_eq!(raffle_client.get_user_winning_rounds(&alice, &5, &2).len(), 0);
+
+    let page = raffle_client.get_user_win_history(&alice, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().round, 2);
+}
+
+#[test]
+fn test_fulfill_rejects_mismatched_request_id() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    let request_id = raffle_client.request_draw(&Address::generate(&env));
+
+    // A callback for a request the round never made is refused
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    let result = vrf_client.try_fulfill_request(&raffle_id, &(request_id + 1), &fixed_randomness(&env, 7));
+    assert!(result.is_err());
+    let result = raffle_client.try_fulfill_random(&vrf_id, &(request_id + 1), &fixed_randomness(&env, 7), &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::StaleVRFRequest)));
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+
+    // Only the VRF contract may call back
+    let outsider = Address::generate(&env);
+    let result = raffle_client.try_fulfill_random(&outsider, &request_id, &fixed_randomness(&env, 7), &outsider);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedVRF)));
+
+    // The recorded request settles the round and is kept with the winner
+    vrf_client.fulfill_request(&raffle_id, &request_id, &fixed_randomness(&env, 7));
+    let winner_record = raffle_client.get_winner(&1).unwrap();
+    assert_eq!(winner_record.vrf_request_id, request_id);
+    assert_eq!(raffle_client.get_round_info(&1).vrf_request_id, Some(request_id));
+}
+
+#[test]
+fn test_replayed_callback_cannot_settle_next_round() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &1_000_000_000i128);
+    token_admin.mint(&bob, &1_000_000_000i128);
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+
+    // Round 1 settles normally
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    let first_request = raffle_client.request_draw(&Address::generate(&env));
+    vrf_client.fulfill_request(&raffle_id, &first_request, &fixed_randomness(&env, 1));
+
+    // Round 2 is waiting on its own request
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    let second_request = raffle_client.request_draw(&Address::generate(&env));
+    assert_ne!(first_request, second_request);
+
+    // Replaying round 1's callback fails
+    let result = raffle_client.try_fulfill_random(&vrf_id, &first_request, &fixed_randomness(&env, 1), &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::StaleVRFRequest)));
+    assert_eq!(raffle_client.get_round_info(&2).state, State::DRAWING);
+
+    vrf_client.fulfill_request(&raffle_id, &second_request, &fixed_randomness(&env, 1));
+    assert_eq!(raffle_client.get_winner(&2).unwrap().vrf_request_id, second_request);
+}
+
+// Switch the raffle to commit-reveal draws with the given reveal window
+fn enable_commit_reveal(raffle_client: &LuckyLedgersRaffleClient, reveal_window: u32) {
+    let mut config = raffle_client.get_config();
+    config.draw_mode = DrawMode::CommitReveal;
+    config.reveal_window = reveal_window;
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_commit_reveal_draw() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    enable_commit_reveal(&raffle_client, 100);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    for user in [&alice, &bob, &charlie] {
+        token_admin.mint(user, &100_000_000i128);
+    }
+
+    let alice_secret = BytesN::from_array(&env, &[1u8; 32]);
+    let bob_secret = BytesN::from_array(&env, &[2u8; 32]);
+    let alice_commitment = commitment::reveal_commitment(&env, 1, &alice, &alice_secret);
+    let bob_commitment = commitment::reveal_commitment(&env, 1, &bob, &bob_secret);
+
+    raffle_client.enter_with_commitment(&alice, &5, &alice_commitment);
+    raffle_client.enter_with_commitment(&bob, &3, &bob_commitment);
+    // Committing is optional
+    raffle_client.enter(&charlie, &1);
+    assert_eq!(raffle_client.get_commitment(&1, &alice), Some(alice_commitment));
+
+    // The VRF round-trip is replaced in this mode
+    assert_eq!(raffle_client.try_request_draw(&Address::generate(&env)), Err(Ok(Error::WrongDrawMode)));
+
+    let deadline = raffle_client.start_reveal();
+    assert_eq!(deadline, env.ledger().sequence() + 100);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::REVEAL);
+    assert_eq!(raffle_client.try_enter(&charlie, &1), Err(Ok(Error::RoundNotOpen)));
+    assert_eq!(raffle_client.try_finalize_reveal(), Err(Ok(Error::RevealWindowOpen)));
+
+    assert_eq!(raffle_client.try_reveal(&bob, &alice_secret), Err(Ok(Error::InvalidReveal)));
+    assert_eq!(raffle_client.try_reveal(&charlie, &alice_secret), Err(Ok(Error::NoCommitment)));
+    raffle_client.reveal(&alice, &alice_secret);
+    assert_eq!(raffle_client.try_reveal(&alice, &alice_secret), Err(Ok(Error::AlreadyRevealed)));
+    raffle_client.reveal(&bob, &bob_secret);
+
+    // Everyone who committed revealed, no need to wait out the window
+    let expected_seed = env.as_contract(&raffle_id, || {
+        let accumulator = BytesN::from_array(&env, &[3u8; 32]);
+        commitment::reveal_seed(&env, 1, &accumulator, deadline)
+    });
+    let winner = raffle_client.finalize_reveal();
+
+    let phase = raffle_client.get_reveal_phase(&1);
+    assert_eq!((phase.commitments, phase.reveals), (2, 2));
+    assert_eq!(raffle_client.get_round_info(&1).draw_seed, Some(expected_seed.clone()));
+
+    let winning_ticket = commitment::draw_index(&env, &expected_seed, commitment::WINNER_DRAW, 9);
+    let expected_winner = match winning_ticket {
+        0..=4 => alice,
+        5..=7 => bob,
+        _ => charlie,
+    };
+    assert_eq!(winner, expected_winner);
+
+    let record = raffle_client.get_winner(&1).unwrap();
+    assert_eq!(record.winner, winner);
+    assert_eq!(record.vrf_request_id, 0);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::COMPLETED);
+    assert_eq!(raffle_client.get_round_info(&2).state, State::OPEN);
+    assert_eq!(raffle_client.audit().surplus, 0);
+}
+
+#[test]
+fn test_commit_reveal_skips_non_revealers() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    enable_commit_reveal(&raffle_client, 100);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for user in [&alice, &bob] {
+        token_admin.mint(user, &100_000_000i128);
+    }
+
+    let alice_secret = BytesN::from_array(&env, &[1u8; 32]);
+    let bob_secret = BytesN::from_array(&env, &[2u8; 32]);
+    raffle_client.enter_with_commitment(&alice, &5, &commitment::reveal_commitment(&env, 1, &alice, &alice_secret));
+    raffle_client.enter_with_commitment(&bob, &4, &commitment::reveal_commitment(&env, 1, &bob, &bob_secret));
+
+    let deadline = raffle_client.start_reveal();
+    raffle_client.reveal(&alice, &alice_secret);
+
+    // Bob holding back keeps the window open until the deadline
+    env.ledger().set_sequence_number(deadline);
+    assert_eq!(raffle_client.try_finalize_reveal(), Err(Ok(Error::RevealWindowOpen)));
+
+    env.ledger().set_sequence_number(deadline + 1);
+    assert_eq!(raffle_client.try_reveal(&bob, &bob_secret), Err(Ok(Error::RevealWindowClosed)));
+    raffle_client.finalize_reveal();
+
+    let phase = raffle_client.get_reveal_phase(&1);
+    assert_eq!((phase.commitments, phase.reveals), (2, 1));
+    assert!(raffle_client.get_winner(&1).is_some());
+    assert_eq!(raffle_client.get_current_round_number(), 2);
+}
+
+#[test]
+fn test_reveal_seed_ignores_finalize_ledger() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    enable_commit_reveal(&raffle_client, 100);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for user in [&alice, &bob] {
+        token_admin.mint(user, &100_000_000i128);
+    }
+
+    let alice_secret = BytesN::from_array(&env, &[1u8; 32]);
+    raffle_client.enter_with_commitment(&alice, &5, &commitment::reveal_commitment(&env, 1, &alice, &alice_secret));
+    raffle_client.enter(&bob, &4);
+    let deadline = raffle_client.start_reveal();
+    raffle_client.reveal(&alice, &alice_secret);
+
+    // Waiting for a favorable ledger doesn't change the seed
+    let expected_seed = env.as_contract(&raffle_id, || commitment::reveal_seed(&env, 1, &alice_secret, deadline));
+    env.ledger().set_sequence_number(deadline + 537);
+    raffle_client.finalize_reveal();
+    assert_eq!(raffle_client.get_round_info(&1).draw_seed, Some(expected_seed));
+}
+
+#[test]
+fn test_round_without_reveals_is_refunded() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    enable_commit_reveal(&raffle_client, 100);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for user in [&alice, &bob] {
+        token_admin.mint(user, &100_000_000i128);
+    }
+
+    let alice_secret = BytesN::from_array(&env, &[1u8; 32]);
+    raffle_client.enter_with_commitment(&alice, &5, &commitment::reveal_commitment(&env, 1, &alice, &alice_secret));
+    raffle_client.enter(&bob, &4);
+    let deadline = raffle_client.start_reveal();
+    assert_eq!(raffle_client.try_enable_refunds(), Err(Ok(Error::RevealWindowOpen)));
+
+    // Nobody revealed, so there is no secret to draw from
+    env.ledger().set_sequence_number(deadline + 1);
+    assert_eq!(raffle_client.try_finalize_reveal(), Err(Ok(Error::NoReveals)));
+
+    assert_eq!(raffle_client.enable_refunds(), 1);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::REFUNDING);
+    raffle_client.claim_refund(&alice, &1);
+    raffle_client.claim_refund(&bob, &1);
+    assert_eq!(token_client.balance(&alice), 100_000_000i128);
+    assert_eq!(token_client.balance(&bob), 100_000_000i128);
+}
+
+#[test]
+fn test_commit_reveal_requires_mode() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    let commitment = BytesN::from_array(&env, &[9u8; 32]);
+
+    assert_eq!(
+        raffle_client.try_enter_with_commitment(&alice, &1, &commitment),
+        Err(Ok(Error::WrongDrawMode))
+    );
+    assert_eq!(raffle_client.try_start_reveal(), Err(Ok(Error::WrongDrawMode)));
+
+    let mut config = raffle_client.get_config();
+    config.draw_mode = DrawMode::CommitReveal;
+    config.reveal_window = 0;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+}
+
+// Fill round 1 to the target and request its draw
+fn start_round_one_draw(raffle_client: &LuckyLedgersRaffleClient, alice: &Address, bob: &Address) -> u64 {
+    raffle_client.enter(alice, &5);
+    raffle_client.enter(bob, &4);
+    raffle_client.request_draw(&Address::generate(&raffle_client.env))
+}
+
+#[test]
+fn test_retry_draw_after_timeout() {
+    use soroban_sdk::testutils::Events as _;
+
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    let first_request = start_round_one_draw(&raffle_client, &alice, &bob);
+    let timeout = raffle_client.get_config().draw_timeout;
+
+    // The VRF still has time to answer
+    env.ledger().with_mut(|li| li.sequence_number += timeout);
+    assert_eq!(raffle_client.try_retry_draw(), Err(Ok(Error::DrawNotTimedOut)));
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let second_request = raffle_client.retry_draw();
+    let retried = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            *contract == raffle_id
+                && Symbol::try_from_val(&env, &topics.get(1).unwrap()).ok() == Some(Symbol::new(&env, "draw_retried"))
+        })
+        .count();
+    assert_eq!(retried, 1);
+    assert_ne!(second_request, first_request);
+
+    let round = raffle_client.get_round_info(&1);
+    assert_eq!(round.state, State::DRAWING);
+    assert_eq!(round.vrf_request_id, Some(second_request));
+    assert_eq!(round.draw_attempts, 2);
+    assert_eq!(round.draw_ledger, env.ledger().sequence());
+
+    // Only the latest request can settle the round
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    assert!(vrf_client.try_fulfill_request(&raffle_id, &first_request, &fixed_randomness(&env, 0)).is_err());
+    vrf_client.fulfill_request(&raffle_id, &second_request, &fixed_randomness(&env, 0));
+    assert_eq!(raffle_client.get_winner(&1).unwrap().vrf_request_id, second_request);
+}
+
+#[test]
+fn test_refunds_after_draw_attempts_exhausted() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_timeout = 10;
+    config.max_draw_attempts = 2;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    start_round_one_draw(&raffle_client, &alice, &bob);
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    assert_eq!(raffle_client.try_enable_refunds(), Err(Ok(Error::DrawAttemptsRemaining)));
+
+    raffle_client.retry_draw();
+    assert_eq!(raffle_client.try_enable_refunds(), Err(Ok(Error::DrawNotTimedOut)));
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    assert_eq!(raffle_client.try_retry_draw(), Err(Ok(Error::DrawAttemptsExhausted)));
+
+    assert_eq!(raffle_client.enable_refunds(), 1);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::REFUNDING);
+    assert_eq!(raffle_client.get_current_round_number(), 2);
+    assert_eq!(raffle_client.audit().liabilities, 9_000_000i128);
+
+    // A late answer can't settle the abandoned round
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    assert!(vrf_client.try_fulfill(&raffle_id, &fixed_randomness(&env, 0)).is_err());
+
+    assert_eq!(raffle_client.claim_refund(&alice, &1), 5_000_000i128);
+    assert_eq!(raffle_client.try_claim_refund(&alice, &1), Err(Ok(Error::AlreadyRefunded)));
+    assert_eq!(raffle_client.try_claim_refund(&charlie, &1), Err(Ok(Error::NothingToRefund)));
+    assert_eq!(raffle_client.claim_refund(&bob, &1), 4_000_000i128);
+
+    assert_eq!(token_client.balance(&alice), 100_000_000i128);
+    assert_eq!(token_client.balance(&bob), 100_000_000i128);
+    let audit = raffle_client.audit();
+    assert_eq!((audit.balance, audit.liabilities), (0, 0));
+}
+
+#[test]
+fn test_refunds_return_what_each_player_paid() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_timeout = 10;
+    config.max_draw_attempts = 1;
+    config.price_tiers = Vec::from_array(
+        &env,
+        [PriceTier { min_tickets: 5, ticket_price: 1_000_000, bonus_tickets: 1 }],
+    );
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    // Alice gets a free ticket, so tickets no longer share one price
+    start_round_one_draw(&raffle_client, &alice, &bob);
+    assert_eq!(token_client.balance(&alice), 96_000_000i128);
+    assert_eq!(token_client.balance(&bob), 96_000_000i128);
+
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    raffle_client.enable_refunds();
+    assert_eq!(raffle_client.claim_refund(&alice, &1), 4_000_000i128);
+    assert_eq!(raffle_client.claim_refund(&bob, &1), 4_000_000i128);
+
+    assert_eq!(token_client.balance(&alice), 100_000_000i128);
+    assert_eq!(token_client.balance(&bob), 100_000_000i128);
+    let audit = raffle_client.audit();
+    assert_eq!((audit.balance, audit.liabilities), (0, 0));
+}
+
+#[test]
+fn test_refunds_share_pool_left_after_bounty() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_timeout = 10;
+    config.max_draw_attempts = 1;
+    config.draw_bounty = KeeperBounty::Fixed(100_000);
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    for player in [&alice, &bob, &charlie] {
+        token_admin.mint(player, &100_000_000i128);
+    }
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &3);
+    raffle_client.enter(&charlie, &1);
+    raffle_client.request_draw(&Address::generate(&env));
+
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    raffle_client.enable_refunds();
+    assert_eq!(raffle_client.audit().liabilities, 8_900_000i128);
+
+    // The 8_900_000 left after the bounty is shared by what each paid, rounding is settled
+    // by the later claims
+    assert_eq!(raffle_client.claim_refund(&alice, &1), 4_944_444i128);
+    assert_eq!(raffle_client.claim_refund(&bob, &1), 2_966_667i128);
+    assert_eq!(raffle_client.claim_refund(&charlie, &1), 988_889i128);
+    assert_eq!(token_client.balance(&charlie), 99_988_889i128);
+
+    let audit = raffle_client.audit();
+    assert_eq!((audit.balance, audit.liabilities), (0, 0));
+}
+
+#[test]
+fn test_refunds_along_price_curve() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_timeout = 10;
+    config.max_draw_attempts = 1;
+    config.price_curve = PriceCurve::Linear(100_000);
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    // Bob's later tickets cost more than Alice's
+    start_round_one_draw(&raffle_client, &alice, &bob);
+    assert_eq!(raffle_client.get_round_stats(&1).prize_pool, 12_600_000i128);
+
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    raffle_client.enable_refunds();
+    assert_eq!(raffle_client.claim_refund(&alice, &1), 6_000_000i128);
+    assert_eq!(raffle_client.claim_refund(&bob, &1), 6_600_000i128);
+
+    assert_eq!(token_client.balance(&alice), 100_000_000i128);
+    assert_eq!(token_client.balance(&bob), 100_000_000i128);
+    assert_eq!(raffle_client.audit().balance, 0);
+}
+
+#[test]
+fn test_single_answer_is_hashed_under_round_request() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    // The second provider's next request ID differs from the first's
+    let second_vrf = env.register(mock_vrf::MockVRF, ());
+    mock_vrf::MockVRFClient::new(&env, &second_vrf).request_random(&Address::generate(&env), &fixed_randomness(&env, 0));
+    let mut config = raffle_client.get_config();
+    config.vrf_providers = Vec::from_array(&env, [vrf_id.clone(), second_vrf.clone()]);
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    let request_id = start_round_one_draw(&raffle_client, &alice, &bob);
+
+    // The second provider answers first and alone settles the round
+    let second_request = raffle_client.get_draw_randomness(&1).unwrap().contributions.get(1).unwrap().request_id;
+    assert_ne!(second_request, request_id);
+    let answer = fixed_randomness(&env, 5);
+    raffle_client.fulfill_random(&second_vrf, &second_request, &answer, &Address::generate(&env));
+
+    let record = raffle_client.get_draw_record(&1).unwrap();
+    assert_eq!(record.seed, answer_seed(&env, &second_vrf, &answer));
+    assert_eq!(record.vrf_request_id, request_id);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().vrf_request_id, request_id);
+    assert_eq!(raffle_client.get_round_info(&1).vrf_request_id, Some(request_id));
+    assert!(raffle_client.verify_draw(&1));
+}
+
+#[test]
+fn test_draw_waits_for_provider_threshold() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let second_vrf = env.register(mock_vrf::MockVRF, ());
+    let third_vrf = env.register(mock_vrf::MockVRF, ());
+    let mut config = raffle_client.get_config();
+    config.vrf_providers = Vec::from_array(&env, [vrf_id.clone(), second_vrf.clone(), third_vrf.clone()]);
+    config.vrf_threshold = 2;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    start_round_one_draw(&raffle_client, &alice, &bob);
+
+    // Every provider was asked
+    let draw = raffle_client.get_draw_randomness(&1).unwrap();
+    assert_eq!(draw.threshold, 2);
+    assert_eq!(draw.contributions.len(), 3);
+    assert!(draw.contributions.iter().all(|contribution| contribution.random_value.is_none()));
+
+    // One answer isn't enough
+    let third_request = draw.contributions.get(2).unwrap().request_id;
+    raffle_client.fulfill_random(&third_vrf, &third_request, &fixed_randomness(&env, 77), &Address::generate(&env));
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+    let draw = raffle_client.get_draw_randomness(&1).unwrap();
+    assert_eq!(draw.contributions.get(2).unwrap().random_value, Some(fixed_randomness(&env, 77)));
+
+    assert_eq!(
+        raffle_client.try_fulfill_random(&third_vrf, &third_request, &fixed_randomness(&env, 78), &Address::generate(&env)),
+        Err(Ok(Error::DuplicateContribution))
+    );
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        raffle_client.try_fulfill_random(&outsider, &third_request, &fixed_randomness(&env, 78), &Address::generate(&env)),
+        Err(Ok(Error::UnauthorizedVRF))
+    );
+
+    // The second answer settles the round from both contributions, in provider order
+    let first_request = draw.contributions.get(0).unwrap().request_id;
+    raffle_client.fulfill_random(&vrf_id, &first_request, &fixed_randomness(&env, 5), &Address::generate(&env));
+
+    let mut answered = draw.contributions.clone();
+    let mut first = answered.get(0).unwrap();
+    first.random_value = Some(fixed_randomness(&env, 5));
+    answered.set(0, first);
+    let seed = commitment::contributions_seed(&env, &answered);
+    let winning_ticket = commitment::draw_index(&env, &seed, commitment::WINNER_DRAW, 9);
+    let expected_winner = if winning_ticket < 5 { alice } else { bob };
+
+    assert_eq!(raffle_client.get_round_info(&1).state, State::COMPLETED);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, expected_winner);
+    assert_eq!(
+        raffle_client.try_fulfill_random(&second_vrf, &1, &fixed_randomness(&env, 9), &Address::generate(&env)),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_provider_config_validation() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let other_vrf = Address::generate(&env);
+    let mut config = raffle_client.get_config();
+
+    config.vrf_providers = Vec::from_array(&env, [vrf_id.clone(), vrf_id.clone()]);
+    config.vrf_threshold = 1;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    config.vrf_providers = Vec::from_array(&env, [vrf_id.clone(), other_vrf]);
+    config.vrf_threshold = 3;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    config.vrf_threshold = 0;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    config.vrf_threshold = 2;
+    raffle_client.update_config(&config);
+    assert_eq!(raffle_client.get_config().vrf_providers.len(), 2);
+}
+
+// Point round one's draws at a single provider of the given kind
+fn use_provider(raffle_client: &LuckyLedgersRaffleClient, provider: &Address, kind: ProviderKind) {
+    let mut config = raffle_client.get_config();
+    config.vrf_providers = Vec::from_array(&raffle_client.env, [provider.clone()]);
+    config.vrf_threshold = 1;
+    config.provider_kind = kind;
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_bundled_vrf_oracle_fulfillment() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let vrf_id = env.register(vrf::MockVRF, (&admin, &oracle));
+    let vrf_client = vrf::MockVRFClient::new(&env, &vrf_id);
+    use_provider(&raffle_client, &vrf_id, ProviderKind::Callback);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    let request_id = start_round_one_draw(&raffle_client, &alice, &bob);
+
+    let request = vrf_client.get_request(&request_id).unwrap();
+    assert_eq!(request.requester, raffle_id);
+    assert_eq!(Some(request.seed), raffle_client.get_round_info(&1).draw_seed);
+
+    // The oracle answers through the VRF, which calls back into the raffle
+    vrf_client.fulfill(&request_id, &answer_for_ticket(&env, &vrf_id, 6, 9));
+    assert_eq!(raffle_client.get_round_info(&1).state, State::COMPLETED);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, bob);
+}
+
+#[test]
+fn test_signed_vrf_fulfillment() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let vrf_id = env.register(vrf::MockVRF, (&admin, &oracle));
+    let vrf_client = vrf::MockVRFClient::new(&env, &vrf_id);
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    vrf_client.set_oracle_public_key(&BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()));
+    use_provider(&raffle_client, &vrf_id, ProviderKind::Callback);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    let request_id = start_round_one_draw(&raffle_client, &alice, &bob);
+
+    // Anyone can relay the oracle's signature, the randomness is derived from it
+    let payload = vrf_client.get_signing_payload(&request_id);
+    let mut payload_bytes = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut payload_bytes);
+    let signature = BytesN::from_array(&env, &signing_key.sign(&payload_bytes).to_bytes());
+    let randomness: BytesN<32> = env.crypto().sha256(&signature.clone().into()).into();
+    vrf_client.fulfill_signed(&request_id, &randomness, &signature, &Address::generate(&env));
+
+    let winning_ticket = commitment::draw_index(&env, &randomness, commitment::WINNER_DRAW, 9);
+    let expected_winner = if winning_ticket < 5 { alice } else { bob };
+
+    assert_eq!(raffle_client.get_round_info(&1).state, State::COMPLETED);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, expected_winner);
+}
+
+#[test]
+fn test_synchronous_provider_settles_on_request() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let provider_id = env.register(sync_provider::SyncProvider, ());
+    let provider_client = sync_provider::SyncProviderClient::new(&env, &provider_id);
+    let answer = answer_for_ticket(&env, &provider_id, 3, 9);
+    provider_client.set_value(&answer);
+    use_provider(&raffle_client, &provider_id, ProviderKind::Synchronous);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    assert_eq!(start_round_one_draw(&raffle_client, &alice, &bob), 0);
+
+    // No callback needed, the draw settled in request_draw
+    assert_eq!(raffle_client.get_round_info(&1).state, State::COMPLETED);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, alice);
+    let draw = raffle_client.get_draw_randomness(&1).unwrap();
+    assert_eq!(draw.contributions.get(0).unwrap().random_value, Some(answer));
+    assert_eq!(raffle_client.get_current_round_number(), 2);
+    assert_eq!(
+        raffle_client.try_fulfill_random(&provider_id, &0, &fixed_randomness(&env, 8), &Address::generate(&env)),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_simulated_rounds_pick_winners_by_ticket_share() {
+    // Alice holds 5 of 9 tickets, Bob 3 and Charlie 1. Rounds run in batches on fresh
+    // environments to keep the test fast.
+    let (batches, rounds_per_batch) = (24u32, 10u32);
+    let mut rng = XorShift(0x5eed_1234_abcd_0001);
+    let mut wins = [0u32; 3];
+
+    for _ in 0..batches {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+        env.mock_all_auths();
+        let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+
+        let players = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        for player in players.iter() {
+            token_admin.mint(player, &10_000_000_000i128);
+        }
+
+        for round in 1..=rounds_per_batch {
+            raffle_client.enter(&players[0], &5);
+            raffle_client.enter(&players[1], &3);
+            raffle_client.enter(&players[2], &1);
+            raffle_client.request_draw(&Address::generate(&env));
+            vrf_client.fulfill(&raffle_id, &rng.randomness(&env));
+
+            let winner = raffle_client.get_winner(&round).unwrap().winner;
+            wins[players.iter().position(|player| *player == winner).unwrap()] += 1;
+        }
+    }
+
+    let rounds = (batches * rounds_per_batch) as f64;
+    let expected = [rounds * 5.0 / 9.0, rounds * 3.0 / 9.0, rounds / 9.0];
+    let statistic: f64 = wins
+        .iter()
+        .zip(expected)
+        .map(|(&observed, expected)| (observed as f64 - expected).powi(2) / expected)
+        .sum();
+
+    // Critical value for 2 degrees of freedom at p = 0.001
+    assert!(statistic < 13.82, "{:?}", wins);
+}
+
+#[test]
+fn test_draw_record_verifies_winner() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    let request_id = start_round_one_draw(&raffle_client, &alice, &bob);
+    assert_eq!(raffle_client.try_verify_draw(&1), Err(Ok(Error::DrawRecordNotFound)));
+
+    // Before the draw anyone can check which ticket a seed picks
+    let randomness = randomness_for_ticket(&env, 7, 9);
+    assert_eq!(raffle_client.preview_winner(&1, &randomness), bob);
+    assert_eq!(raffle_client.preview_winner(&1, &randomness_for_ticket(&env, 2, 9)), alice);
+
+    let answer = answer_for_ticket(&env, &vrf_id, 7, 9);
+    mock_vrf::MockVRFClient::new(&env, &vrf_id).fulfill(&raffle_id, &answer);
+
+    let record = raffle_client.get_draw_record(&1).unwrap();
+    let participants_root = raffle_client.get_round_info(&1).participants_root.unwrap();
+    assert_eq!(
+        record,
+        DrawRecord {
+            seed: answer_seed(&env, &vrf_id, &answer),
+            winning_ticket: 7,
+            total_tickets: 9,
+            participants_root,
+            vrf_request_id: request_id,
+            winner: bob.clone(),
+        }
+    );
+    assert!(raffle_client.verify_draw(&1));
+    assert_eq!(raffle_client.preview_winner(&1, &record.seed), record.winner);
+
+    // Ticket entries changed after the draw no longer match
+    let winning_entry = raffle_client.get_ticket_entry(&1, &1).unwrap();
+    env.as_contract(&raffle_id, || {
+        let forged = TicketEntry { participant: alice.clone(), ..winning_entry.clone() };
+        env.storage().persistent().set(&types::Storage::TicketEntry(1, 1), &forged);
+    });
+    assert!(!raffle_client.verify_draw(&1));
+    env.as_contract(&raffle_id, || {
+        env.storage().persistent().set(&types::Storage::TicketEntry(1, 1), &winning_entry);
+    });
+    assert!(raffle_client.verify_draw(&1));
+
+    // And so does a tree that no longer has the recorded root
+    env.as_contract(&raffle_id, || {
+        let mut tree = storage::get_participants_tree(&env, 1);
+        merkle::append(&env, &mut tree, &merkle::leaf_hash(&env, &winning_entry));
+        storage::set_participants_tree(&env, 1, &tree);
+    });
+    assert!(!raffle_client.verify_draw(&1));
+
+    // Pruned rounds can't be recomputed, the record stays
+    raffle_client.prune_round(&1, &10);
+    assert_eq!(raffle_client.try_verify_draw(&1), Err(Ok(Error::RoundPruned)));
+    assert_eq!(raffle_client.get_draw_record(&1), Some(record));
+
+    // Empty rounds have no winner to preview
+    assert_eq!(
+        raffle_client.try_preview_winner(&2, &randomness),
+        Err(Ok(Error::WinnerNotFound))
+    );
+}
+
+// Inclusion proof for leaf `index`, built off-chain from every leaf of the tree
+fn merkle_proof(env: &Env, leaves: &[BytesN<32>], index: u32) -> Vec<BytesN<32>> {
+    let mut level: std::vec::Vec<BytesN<32>> = leaves.to_vec();
+    let mut zero = BytesN::from_array(env, &[0u8; 32]);
+    let mut idx = index as usize;
+    let mut proof = Vec::new(env);
+
+    for _ in 0..merkle::TREE_DEPTH {
+        proof.push_back(level.get(idx ^ 1).cloned().unwrap_or(zero.clone()));
+        level = level
+            .chunks(2)
+            .map(|pair| merkle::node_hash(env, &pair[0], pair.get(1).unwrap_or(&zero)))
+            .collect();
+        zero = merkle::node_hash(env, &zero, &zero);
+        idx >>= 1;
+    }
+    proof
+}
+
+#[test]
+fn test_incremental_root_matches_full_tree() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let mut tree = merkle::empty(&env);
+    let mut leaves = std::vec::Vec::new();
+    for count in 0..12u32 {
+        // Root of the full tree, proved through any of its leaves (or the empty tree)
+        let expected = if leaves.is_empty() {
+            let mut zero = BytesN::from_array(&env, &[0u8; 32]);
+            for _ in 0..merkle::TREE_DEPTH {
+                zero = merkle::node_hash(&env, &zero, &zero);
+            }
+            zero
+        } else {
+            let proof = merkle_proof(&env, &leaves, 0);
+            let root = merkle::root(&env, &tree);
+            assert!(merkle::verify_proof(&env, &root, &leaves[0], 0, &proof));
+            root
+        };
+        assert_eq!(merkle::root(&env, &tree), expected);
+
+        let entry = TicketEntry {
+            participant: Address::generate(&env),
+            first_ticket: count,
+            tickets: 1,
+        };
+        let leaf = merkle::leaf_hash(&env, &entry);
+        merkle::append(&env, &mut tree, &leaf);
+        leaves.push(leaf);
+    }
+
+    let root = merkle::root(&env, &tree);
+    for (index, leaf) in leaves.iter().enumerate() {
+        let proof = merkle_proof(&env, &leaves, index as u32);
+        assert!(merkle::verify_proof(&env, &root, leaf, index as u32, &proof));
+        assert!(!merkle::verify_proof(&env, &root, leaf, index as u32 ^ 1, &proof));
+    }
+
+    let mut short_proof = merkle_proof(&env, &leaves, 0);
+    short_proof.pop_back();
+    assert!(!merkle::verify_proof(&env, &root, &leaves[0], 0, &short_proof));
+}
+
+#[test]
+fn test_participants_root_proves_ticket_ranges() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.target_tickets = 8;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    // Every purchase is its own range, in purchase order
+    raffle_client.enter(&alice, &2);
+    raffle_client.enter(&bob, &3);
+    raffle_client.enter(&alice, &3);
+    let entries = [
+        TicketEntry { participant: alice.clone(), first_ticket: 0, tickets: 2 },
+        TicketEntry { participant: bob.clone(), first_ticket: 2, tickets: 3 },
+        TicketEntry { participant: alice.clone(), first_ticket: 5, tickets: 3 },
+    ];
+    for (index, entry) in entries.iter().enumerate() {
+        assert_eq!(raffle_client.get_ticket_entry(&1, &(index as u32)), Some(entry.clone()));
+    }
+    let leaves: std::vec::Vec<BytesN<32>> = entries.iter().map(|entry| merkle::leaf_hash(&env, entry)).collect();
+
+    // The root is frozen when sales close
+    assert_eq!(raffle_client.get_round_info(&1).participants_root, None);
+    let open_root = raffle_client.get_participants_root(&1);
+    raffle_client.request_draw(&Address::generate(&env));
+    let root = raffle_client.get_round_info(&1).participants_root.unwrap();
+    assert_eq!(root, open_root);
+    assert_eq!(raffle_client.get_participants_root(&1), root);
+
+    // The winner is looked up by the same ranges
+    let randomness = randomness_for_ticket(&env, 4, 8);
+    assert_eq!(raffle_client.preview_winner(&1, &randomness), bob);
+    assert_eq!(raffle_client.preview_winner(&1, &randomness_for_ticket(&env, 6, 8)), alice);
+    mock_vrf::MockVRFClient::new(&env, &vrf_id).fulfill(&raffle_id, &answer_for_ticket(&env, &vrf_id, 4, 8));
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, bob);
+
+    // The draw record and prune summary commit to the participants through the same root
+    assert_eq!(raffle_client.get_draw_record(&1).unwrap().participants_root, root);
+
+    // Proofs keep working once per-participant data is pruned
+    raffle_client.prune_round(&1, &10);
+    assert_eq!(raffle_client.get_round_summary(&1).unwrap().participants_root, root);
+    assert!(raffle_client.get_ticket_entry(&1, &0).is_none());
+    for (index, entry) in entries.iter().enumerate() {
+        let proof = merkle_proof(&env, &leaves, index as u32);
+        assert!(raffle_client.verify_inclusion(&1, entry, &(index as u32), &proof));
+    }
+
+    // Claiming more tickets than were bought fails
+    let mut inflated = entries[1].clone();
+    inflated.tickets = 4;
+    let proof = merkle_proof(&env, &leaves, 1);
+    assert!(!raffle_client.verify_inclusion(&1, &inflated, &1, &proof));
+}
+
+// Turn on drawing from the entry that reaches the target
+fn enable_auto_draw(raffle_client: &LuckyLedgersRaffleClient) {
+    let mut config = raffle_client.get_config();
+    config.auto_draw = true;
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_auto_draw_on_target_entry() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+    enable_auto_draw(&raffle_client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::OPEN);
+
+    // Bob's entry reaches the target and requests the draw in the same call
+    raffle_client.enter(&bob, &4);
+    let round = raffle_client.get_round_info(&1);
+    assert_eq!(round.state, State::DRAWING);
+    assert!(round.draw_seed.is_some());
+    assert_eq!(round.participants_root, Some(raffle_client.get_participants_root(&1)));
+    assert_eq!(raffle_client.try_request_draw(&Address::generate(&env)), Err(Ok(Error::InvalidState)));
+
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill(&raffle_id, &answer_for_ticket(&env, &vrf_id, 6, 9));
+    assert_eq!(raffle_client.get_winner(&1).unwrap().winner, bob);
+}
+
+#[test]
+fn test_failed_auto_draw_keeps_entry() {
+    use soroban_sdk::{testutils::Events as _, Event as _};
+
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    // No contract lives at this address, so the randomness request fails
+    let missing_provider = Address::generate(&env);
+    use_provider(&raffle_client, &missing_provider, ProviderKind::Callback);
+    enable_auto_draw(&raffle_client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+
+    let expected = events::AutoDrawFailed { round: 1 };
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(contract, topics, _)| contract == raffle_id && topics == expected.topics(&env)));
+
+    // The entry stands and the round is still open with no draw recorded
+    let round = raffle_client.get_round_info(&1);
+    assert_eq!(round.state, State::OPEN);
+    assert_eq!(round.draw_seed, None);
+    assert_eq!(raffle_client.get_round_stats(&1).total_tickets, 9);
+    assert_eq!(token_client.balance(&bob), 96_000_000i128);
+    assert_eq!(raffle_client.try_request_draw(&Address::generate(&env)), Err(Ok(Error::VRFRequestFailed)));
+
+    // Providers can't be swapped under a round that already sold tickets
+    let mut config = raffle_client.get_config();
+    config.vrf_providers = Vec::from_array(&env, [vrf_id]);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::ConfigLocked)));
+}
+
+#[test]
+fn test_auto_draw_rejects_synchronous_provider() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    // The entrant reaching the target could simulate a synchronous answer before submitting
+    let provider_id = env.register(sync_provider::SyncProvider, ());
+    use_provider(&raffle_client, &provider_id, ProviderKind::Synchronous);
+    let mut config = raffle_client.get_config();
+    config.auto_draw = true;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+}
+
+#[test]
+fn test_auto_draw_pays_no_draw_bounty() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    set_bounties(&raffle_client, KeeperBounty::BasisPoints(100), KeeperBounty::None);
+    enable_auto_draw(&raffle_client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+
+    // Bob's entry started the draw but he only paid for his tickets
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+    assert_eq!(token_client.balance(&bob), 96_000_000i128);
+    let stats = raffle_client.get_round_stats(&1);
+    assert_eq!(stats.keeper_bounties, 0);
+    assert_eq!(stats.prize_pool, 9_000_000);
+}
+
+#[test]
+fn test_auto_draw_requires_vrf_mode() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_mode = DrawMode::CommitReveal;
+    config.reveal_window = 100;
+    config.auto_draw = true;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+}
+
+// Set the keeper bounties paid on draw and fulfillment
+fn set_bounties(raffle_client: &LuckyLedgersRaffleClient, draw_bounty: KeeperBounty, fulfill_bounty: KeeperBounty) {
+    let mut config = raffle_client.get_config();
+    config.draw_bounty = draw_bounty;
+    config.fulfill_bounty = fulfill_bounty;
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_keeper_bounties_paid_from_pool() {
+    use soroban_sdk::{testutils::Events as _, xdr::ScVal, Event as _};
+
+    let env = Env::default();
+    let to_scval = |val: &Val| ScVal::try_from_val(&env, val).unwrap();
+    let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+    set_bounties(&raffle_client, KeeperBounty::BasisPoints(100), KeeperBounty::Fixed(50_000));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+
+    // 1% of the 9_000_000 pool goes to whoever requests the draw
+    let keeper = Address::generate(&env);
+    raffle_client.request_draw(&keeper);
+    let expected = events::KeeperPaid {
+        round: 1,
+        keeper: keeper.clone(),
+        task: KeeperTask::Draw,
+        amount: 90_000,
+        prize_pool: 8_910_000,
+    };
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, expected.topics(&env));
+    assert_eq!(to_scval(&data), to_scval(&expected.data(&env)));
+    assert_eq!(token_client.balance(&keeper), 90_000);
+
+    // The fulfillment bounty goes to the keeper the provider reports
+    let relayer = Address::generate(&env);
+    let vrf_client = mock_vrf::MockVRFClient::new(&env, &vrf_id);
+    vrf_client.fulfill_by(&raffle_id, &relayer, &fixed_randomness(&env, 4));
+    assert_eq!(token_client.balance(&relayer), 50_000);
+
+    // Both come out of the pool before the prize is fixed
+    let stats = raffle_client.get_round_stats(&1);
+    assert_eq!(stats.keeper_bounties, 140_000);
+    assert_eq!(stats.prize_pool, 8_860_000);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().amount, 8_860_000);
+    assert_eq!(raffle_client.audit().surplus, 0);
+}
+
+#[test]
+fn test_synchronous_draw_pays_both_bounties_to_keeper() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let provider_id = env.register(sync_provider::SyncProvider, ());
+    use_provider(&raffle_client, &provider_id, ProviderKind::Synchronous);
+    set_bounties(&raffle_client, KeeperBounty::Fixed(100_000), KeeperBounty::BasisPoints(50));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+
+    // The caller requested and fulfilled the draw: 100_000 then 0.5% of the remaining 8_900_000
+    let keeper = Address::generate(&env);
+    raffle_client.request_draw(&keeper);
+    assert_eq!(token_client.balance(&keeper), 144_500);
+    assert_eq!(raffle_client.get_round_stats(&1).keeper_bounties, 144_500);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().amount, 8_855_500);
+}
+
+#[test]
+fn test_bounties_must_leave_part_of_the_smallest_pool() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, vrf_id) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    // Nine tickets at 1_000_000 make the smallest drawable pool 9_000_000
+    let mut config = raffle_client.get_config();
+    config.draw_bounty = KeeperBounty::Fixed(5_000_000);
+    config.fulfill_bounty = KeeperBounty::Fixed(4_000_000);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    config.fulfill_bounty = KeeperBounty::BasisPoints(5_000);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    // Tiers lower the bound to their cheapest price per ticket: 5 for the price of 1
+    config.draw_bounty = KeeperBounty::Fixed(2_000_000);
+    config.fulfill_bounty = KeeperBounty::None;
+    config.price_tiers = Vec::from_array(&env, [PriceTier { min_tickets: 5, ticket_price: 1_000_000, bonus_tickets: 4 }]);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    config.price_tiers = Vec::new(&env);
+
+    // Just under the pool, something is always left for the winner
+    config.draw_bounty = KeeperBounty::Fixed(5_000_000);
+    config.fulfill_bounty = KeeperBounty::Fixed(3_999_999);
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    let keeper = Address::generate(&env);
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    raffle_client.request_draw(&keeper);
+    assert_eq!(token_client.balance(&keeper), 5_000_000);
+
+    let relayer = Address::generate(&env);
+    mock_vrf::MockVRFClient::new(&env, &vrf_id).fulfill_by(&raffle_id, &relayer, &fixed_randomness(&env, 4));
+    assert_eq!(token_client.balance(&relayer), 3_999_999);
+    assert_eq!(raffle_client.get_winner(&1).unwrap().amount, 1);
+}
+
+#[test]
+fn test_keeper_bounty_validation() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.draw_bounty = KeeperBounty::Fixed(-1);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    config.draw_bounty = KeeperBounty::BasisPoints(6_000);
+    config.fulfill_bounty = KeeperBounty::BasisPoints(5_000);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    // All of the pool is too much as well
+    config.fulfill_bounty = KeeperBounty::BasisPoints(4_000);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    config.fulfill_bounty = KeeperBounty::BasisPoints(3_000);
+    raffle_client.update_config(&config);
+    assert_eq!(raffle_client.get_config().fulfill_bounty, KeeperBounty::BasisPoints(3_000));
+}
+
+#[test]
+fn test_max_total_tickets_closes_sales() {
+    use soroban_sdk::{testutils::Events as _, Event as _};
+
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.max_total_tickets = 10;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+    for player in [&alice, &bob, &charlie, &dave] {
+        token_admin.mint(player, &100_000_000i128);
+    }
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+
+    // Charlie asks for 5 but only the last ticket is left, and pays for one
+    assert_eq!(raffle_client.enter(&charlie, &5), 1);
+    let expected = events::SoldOut { round: 1, total_tickets: 10 };
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(contract, topics, _)| contract == raffle_id && topics == expected.topics(&env)));
+    assert_eq!(token_client.balance(&charlie), 99_000_000i128);
+
+    let stats = raffle_client.get_round_stats(&1);
+    assert_eq!(stats.total_tickets, 10);
+    assert_eq!(stats.prize_pool, 10_000_000i128);
+
+    assert_eq!(raffle_client.try_enter(&dave, &1), Err(Ok(Error::SoldOut)));
+    assert_eq!(raffle_client.try_enter(&alice, &0), Err(Ok(Error::SoldOut)));
+    assert!(raffle_client.is_ready_to_draw());
+}
+
+#[test]
+fn test_min_participants_gates_draw() {
+    use soroban_sdk::{testutils::Events as _, Event as _};
+
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.min_participants = 3;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    for player in [&alice, &bob, &charlie] {
+        token_admin.mint(player, &100_000_000i128);
+    }
+
+    // The target is met by two players, not enough to draw
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &4);
+    assert!(!raffle_client.is_ready_to_draw());
+    assert_eq!(
+        raffle_client.try_request_draw(&Address::generate(&env)),
+        Err(Ok(Error::NotEnoughParticipants))
+    );
+
+    // A third participant makes the round drawable and is the one announcing it
+    raffle_client.enter(&charlie, &1);
+    let expected = events::ReadyToDraw { round: 1, total_tickets: 10 };
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .any(|(contract, topics, _)| contract == raffle_id && topics == expected.topics(&env)));
+    assert!(raffle_client.is_ready_to_draw());
+    raffle_client.request_draw(&Address::generate(&env));
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+}
+
+#[test]
+fn test_ticket_cap_validation() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+
+    // Below the target the round could sell out without being drawable
+    let mut config = raffle_client.get_config();
+    config.max_total_tickets = 8;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    // Two players of 5 tickets could buy out 9 tickets, short of 3 participants
+    config.max_total_tickets = 9;
+    config.min_participants = 3;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    config.min_participants = 0;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    config.max_total_tickets = 15;
+    config.min_participants = 3;
+    raffle_client.update_config(&config);
+    assert_eq!(raffle_client.get_config().max_total_tickets, 15);
+}
+
+// Require `close_window` ledgers between sales closing and the draw
+fn set_close_window(raffle_client: &LuckyLedgersRaffleClient, close_window: u32) {
+    let mut config = raffle_client.get_config();
+    config.close_window = close_window;
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_close_window_delays_draw() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    set_close_window(&raffle_client, 10);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    for player in [&alice, &bob, &charlie] {
+        token_admin.mint(player, &100_000_000i128);
+    }
+
+    raffle_client.enter(&alice, &5);
+    assert_eq!(raffle_client.try_close_sales(), Err(Ok(Error::TargetNotMet)));
+    raffle_client.enter(&bob, &4);
+
+    // Meeting the target isn't enough, sales have to close first
+    assert!(!raffle_client.is_ready_to_draw());
+    assert_eq!(raffle_client.try_request_draw(&keeper), Err(Ok(Error::SalesOpen)));
+
+    let closed_ledger = env.ledger().sequence();
+    assert_eq!(raffle_client.close_sales(), closed_ledger + 10);
+    let round = raffle_client.get_round_info(&1);
+    assert_eq!(round.state, State::CLOSED);
+    assert_eq!(round.closed_ledger, closed_ledger);
+    assert_eq!(round.participants_root, Some(raffle_client.get_participants_root(&1)));
+
+    // No entries and no draw until the window has passed
+    assert_eq!(raffle_client.try_enter(&charlie, &1), Err(Ok(Error::SalesClosed)));
+    assert!(!raffle_client.is_ready_to_draw());
+    assert_eq!(raffle_client.try_request_draw(&keeper), Err(Ok(Error::CloseWindowOpen)));
+
+    env.ledger().with_mut(|li| li.sequence_number += 9);
+    assert_eq!(raffle_client.try_request_draw(&keeper), Err(Ok(Error::CloseWindowOpen)));
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert!(raffle_client.is_ready_to_draw());
+    raffle_client.request_draw(&keeper);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+    assert_eq!(raffle_client.try_close_sales(), Err(Ok(Error::InvalidState)));
+}
+
+#[test]
+fn test_sold_out_round_closes_itself() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    set_close_window(&raffle_client, 10);
+    let mut config = raffle_client.get_config();
+    config.max_total_tickets = 9;
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    raffle_client.enter(&alice, &5);
+    raffle_client.enter(&bob, &5);
+
+    let round = raffle_client.get_round_info(&1);
+    assert_eq!(round.state, State::CLOSED);
+    assert_eq!(round.closed_ledger, env.ledger().sequence());
+    assert_eq!(raffle_client.try_enter(&bob, &1), Err(Ok(Error::SoldOut)));
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    raffle_client.request_draw(&Address::generate(&env));
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+}
+
+#[test]
+fn test_auto_draw_waits_for_close_window() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin, _) = setup_integration_test(&env);
+    env.mock_all_auths();
+    set_close_window(&raffle_client, 10);
+    enable_auto_draw(&raffle_client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    raffle_client.enter(&alice, &5);
+
+    // The target entry only closes sales, the draw is requested after the window
+    raffle_client.enter(&bob, &4);
+    assert_eq!(raffle_client.get_round_info(&1).state, State::CLOSED);
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    raffle_client.request_draw(&Address::generate(&env));
+    assert_eq!(raffle_client.get_round_info(&1).state, State::DRAWING);
+}
@@ -0,0 +1,56 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    storage::{self, ALL_TIME_SEASON},
+    types::{LeaderboardEntry, LeaderboardKind},
+};
+
+/// Maximum number of entries kept on each leaderboard
+pub const LEADERBOARD_SIZE: u32 = 50;
+
+/// Add `amount` to a player's running total for the current season and all-time,
+/// re-ranking both leaderboards
+pub fn record(env: &Env, kind: LeaderboardKind, player: &Address, amount: i128) {
+    let season = storage::get_current_season(env);
+    record_for_season(env, kind, season, player, amount);
+    record_for_season(env, kind, ALL_TIME_SEASON, player, amount);
+}
+
+fn record_for_season(env: &Env, kind: LeaderboardKind, season: u32, player: &Address, amount: i128) {
+    let total = storage::get_player_total(env, kind, season, player)
+        .checked_add(amount)
+        .unwrap();
+    storage::set_player_total(env, kind, season, player, total);
+
+    let mut board = storage::get_leaderboard(env, kind, season);
+
+    // Drop the player's previous entry, their total only ever grows
+    if let Some(idx) = board.iter().position(|entry| entry.player == *player) {
+        board.remove(idx as u32);
+    }
+
+    // Find the sorted position, ties keep the earlier entry ahead
+    let position = board
+        .iter()
+        .position(|entry| entry.total < total)
+        .map(|idx| idx as u32)
+        .unwrap_or(board.len());
+
+    if position >= LEADERBOARD_SIZE {
+        return;
+    }
+
+    board.insert(
+        position,
+        LeaderboardEntry {
+            player: player.clone(),
+            total,
+        },
+    );
+
+    if board.len() > LEADERBOARD_SIZE {
+        board.pop_back();
+    }
+
+    storage::set_leaderboard(env, kind, season, &board);
+}
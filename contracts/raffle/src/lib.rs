@@ -1,13 +1,21 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+use randomness::{RandomnessProviderClient, SyncRandomnessProviderClient};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
 
+mod commitment;
 mod error;
 mod events;
+mod leaderboard;
+mod merkle;
+mod pricing;
 mod storage;
 mod types;
 
 use error::Error;
-use types::{Config, Round, RoundStats, State, WinnerRecord};
+use types::{
+    Audit, Config, Contribution, DrawMode, DrawRandomness, DrawRecord, EntryQuote, KeeperBounty, KeeperTask, LeaderboardEntry, LeaderboardKind, ParticipantBucket, PriceCurve, ProviderKind,
+    RevealPhase, Round, RoundStats, TicketEntry, RoundSummary, State, WinnerRecord,
+};
 
 #[contract]
 pub struct LuckyLedgersRaffle;
@@ -27,11 +35,25 @@ impl LuckyLedgersRaffle {
         storage::set_admin(env, &admin);
 
         let config: Config = Config {
-            vrf_contract,
+            vrf_providers: Vec::from_array(env, [vrf_contract]),
+            vrf_threshold: 1,
+            provider_kind: ProviderKind::Callback,
             underlying_token,
             ticket_price,
+            price_tiers: Vec::new(env),
+            price_curve: PriceCurve::Flat,
             target_tickets,
-            max_tickets_per_participant
+            max_total_tickets: 0,
+            min_participants: 1,
+            max_tickets_per_participant,
+            draw_mode: DrawMode::Vrf,
+            auto_draw: false,
+            reveal_window: storage::DEFAULT_REVEAL_WINDOW,
+            draw_timeout: storage::DEFAULT_DRAW_TIMEOUT,
+            max_draw_attempts: storage::DEFAULT_MAX_DRAW_ATTEMPTS,
+            close_window: 0,
+            draw_bounty: KeeperBounty::None,
+            fulfill_bounty: KeeperBounty::None,
         };
 
         storage::set_config(env, config);
@@ -41,157 +63,383 @@ impl LuckyLedgersRaffle {
     /// Enter the current raffle round by buying tickets
     pub fn enter(env: Env, caller: Address, num_tickets: u32) -> Result<u32, Error> {
         caller.require_auth();
+        Self::enter_round(&env, &caller, num_tickets, None)
+    }
+
+    /// Buy exactly `num_tickets` for at most `max_cost`, failing instead of buying fewer
+    pub fn enter_exact(env: Env, caller: Address, num_tickets: u32, max_cost: i128) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::enter_round(&env, &caller, num_tickets, Some(max_cost))
+    }
+
+    /// Enter a commit-reveal round, committing to sha256(caller xdr || round || secret).
+    /// Entering again replaces the commitment while the round is open.
+    pub fn enter_with_commitment(
+        env: Env,
+        caller: Address,
+        num_tickets: u32,
+        commitment: BytesN<32>,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+
+        let config = storage::get_config(&env)?;
+        if config.draw_mode != DrawMode::CommitReveal {
+            return Err(Error::WrongDrawMode);
+        }
+
+        let user_total_tickets = Self::enter_round(&env, &caller, num_tickets, None)?;
+        if user_total_tickets == 0 {
+            return Err(Error::InsufficientTickets);
+        }
+
+        let round_num = storage::get_current_round_number(&env);
+        if storage::get_commitment(&env, round_num, &caller).is_none() {
+            let mut phase = storage::get_reveal_phase(&env, round_num);
+            phase.commitments = phase.commitments.checked_add(1).unwrap();
+            storage::set_reveal_phase(&env, round_num, &phase);
+        }
+        storage::set_commitment(&env, round_num, &caller, &commitment);
+
+        Ok(user_total_tickets)
+    }
 
+    /// End sales on a drawable round and start its close window (anyone can call).
+    /// Returns the first ledger a draw can be requested on.
+    pub fn close_sales(env: Env) -> Result<u32, Error> {
         let config = storage::get_config(&env)?;
         let round = storage::get_current_round(&env)?;
-        let round_num = round.round;
 
-        // Validate round state
         if round.state != State::OPEN {
-            return Err(Error::RoundNotOpen);
+            return Err(Error::InvalidState);
         }
 
-        // Get current round stats
-        let mut stats = storage::get_round_stats(&env, round_num)?;
+        let stats = storage::get_round_stats(&env, round.round)?;
+        Self::check_draw_requirements(&config, &stats)?;
 
-        // Check if this is user's first entry (new participant)
-        let previous_tickets = storage::get_user_tickets(&env, round_num, &caller);
-        let is_new_participant = previous_tickets == 0;
+        let draw_ledger = Self::close_round(&env, &config, round.round, stats.total_tickets);
 
-        // Auto-cap: ensure user doesn't exceed max_tickets_per_participant
-        let remaining_allowance = config.max_tickets_per_participant.saturating_sub(previous_tickets);
-        let tickets_to_buy = num_tickets.min(remaining_allowance);
+        storage::extend_instance_ttl(&env);
+        Ok(draw_ledger)
+    }
 
-        // If user is already at cap or tries to buy 0, return current total
-        if tickets_to_buy == 0 {
-            return Ok(previous_tickets);
+    /// Request a random number draw (anyone can call once target met and the close window passed).
+    /// The keeper is paid the configured draw bounty from the pool.
+    pub fn request_draw(env: Env, keeper: Address) -> Result<u64, Error> {
+        keeper.require_auth();
+
+        let config = storage::get_config(&env)?;
+        if config.draw_mode != DrawMode::Vrf {
+            return Err(Error::WrongDrawMode);
         }
 
-        // Calculate payment amount based on tickets actually being purchased
-        let amount = (tickets_to_buy as i128)
-            .checked_mul(config.ticket_price)
-            .unwrap();
+        let round = storage::get_current_round(&env)?;
+        let round_num = round.round;
 
-        // Transfer tokens from caller to contract
-        let token_client = Self::token_client(&env, config.underlying_token.clone());
-        token_client.transfer(&caller, &env.current_contract_address(), &amount);
+        // Validate round state
+        Self::check_close_window(&env, &config, &round)?;
 
-        // Here the transfered amount could be deposited into Blend or DeFindex
+        let stats = storage::get_round_stats(&env, round_num)?;
+        Self::check_draw_requirements(&config, &stats)?;
 
-        // Add tickets for user
-        storage::add_user_tickets(&env, round_num, &caller, tickets_to_buy);
-        let user_total_tickets = previous_tickets + tickets_to_buy;
+        let request_id = Self::start_draw(&env, &config, round_num, stats.total_tickets, Some(&keeper))?;
 
-        // If new participant, add to participant bucket
-        if is_new_participant {
-            storage::add_participant(&env, round_num, &caller);
-            // Re-fetch stats after add_participant updates it
-            stats = storage::get_round_stats(&env, round_num)?;
-        }
+        storage::extend_instance_ttl(&env);
+        Self::debug_assert_solvent(&env);
+        Ok(request_id)
+    }
 
-        // Update round stats
-        stats.total_tickets = stats.total_tickets.checked_add(tickets_to_buy).unwrap();
-        stats.prize_pool = stats.prize_pool.checked_add(amount).unwrap();
-        storage::set_round_stats(&env, round_num, &stats);
+    /// Re-request randomness for a draw the VRF hasn't answered within `draw_timeout` ledgers
+    /// (anyone can call). The previous request can no longer settle the round.
+    pub fn retry_draw(env: Env) -> Result<u64, Error> {
+        let config = storage::get_config(&env)?;
+        let round = storage::get_current_round(&env)?;
+        let round_num = round.round;
 
-        // Emit event
-        events::emit_player_entered(&env, round_num, &caller, tickets_to_buy, stats.total_tickets);
+        if round.state != State::DRAWING {
+            return Err(Error::InvalidState);
+        }
+        if !Self::draw_timed_out(&env, &config, &round) {
+            return Err(Error::DrawNotTimedOut);
+        }
+        if round.draw_attempts >= config.max_draw_attempts {
+            return Err(Error::DrawAttemptsExhausted);
+        }
 
-        // Check if we've reached target tickets
-        if stats.total_tickets >= config.target_tickets {
-            events::emit_ready_to_draw(&env, round_num, stats.total_tickets);
+        let stats = storage::get_round_stats(&env, round_num)?;
+        let (draw, seed) = Self::request_randomness(&env, &config, round_num, stats.total_tickets)?;
+        if config.provider_kind == ProviderKind::Synchronous {
+            Self::check_settles(&env, round_num, &draw)?;
+        }
+        let request_id = Self::record_draw(&env, round_num, &draw, &seed);
+
+        events::emit_draw_retried(
+            &env,
+            round_num,
+            round.draw_attempts + 1,
+            round.vrf_request_id.unwrap_or_default(),
+            request_id,
+            &seed,
+        );
+
+        // Nobody is rewarded for a retry, the keeper who requested the draw was already paid
+        if config.provider_kind == ProviderKind::Synchronous {
+            Self::settle_draw(&env, round_num, None)?;
         }
 
         storage::extend_instance_ttl(&env);
-        Ok(user_total_tickets)
+        Ok(request_id)
     }
 
-    /// Request a random number draw (anyone can call once target met)
-    pub fn request_draw(env: Env) -> Result<u64, Error> {
+    /// Abandon a draw after every attempt timed out, or a reveal phase that ended without any
+    /// reveal, and let players reclaim their tickets, starting the next round. Only callable by
+    /// admin. Returns the refunded round.
+    pub fn enable_refunds(env: Env) -> Result<u32, Error> {
+        Self::require_admin(&env);
+
         let config = storage::get_config(&env)?;
         let round = storage::get_current_round(&env)?;
         let round_num = round.round;
 
-        // Validate round state
-        if round.state != State::OPEN {
-            return Err(Error::InvalidState);
+        match round.state {
+            State::DRAWING => {
+                if round.draw_attempts < config.max_draw_attempts {
+                    return Err(Error::DrawAttemptsRemaining);
+                }
+                if !Self::draw_timed_out(&env, &config, &round) {
+                    return Err(Error::DrawNotTimedOut);
+                }
+            }
+            State::REVEAL => {
+                let phase = storage::get_reveal_phase(&env, round_num);
+                if env.ledger().sequence() <= phase.deadline_ledger {
+                    return Err(Error::RevealWindowOpen);
+                }
+                if phase.reveals > 0 {
+                    return Err(Error::InvalidState);
+                }
+            }
+            _ => return Err(Error::InvalidState),
         }
 
-        // Check if target tickets met
+        // Move the pool from the open round to refunds owed
         let stats = storage::get_round_stats(&env, round_num)?;
-        if stats.total_tickets < config.target_tickets {
-            return Err(Error::TargetNotMet);
+        let mut liabilities = storage::get_liabilities(&env);
+        liabilities.open_pool = liabilities.open_pool.checked_sub(stats.prize_pool).unwrap();
+        liabilities.refunds = liabilities.refunds.checked_add(stats.prize_pool).unwrap();
+        storage::set_liabilities(&env, &liabilities);
+
+        storage::set_round_state(&env, round_num, State::REFUNDING);
+        events::emit_refunds_enabled(&env, round_num, round.draw_attempts, stats.prize_pool);
+
+        storage::create_new_round(&env);
+        events::emit_round_started(&env, storage::get_current_round_number(&env));
+
+        storage::extend_instance_ttl(&env);
+        Self::debug_assert_solvent(&env);
+        Ok(round_num)
+    }
+
+    /// Reclaim what a player paid for their tickets in a refunded round. Keeper bounties already
+    /// paid from the pool are not returned, each player gets back their share of what is left.
+    pub fn claim_refund(env: Env, claimer: Address, round: u32) -> Result<i128, Error> {
+        claimer.require_auth();
+
+        // CHECKS
+        let round_data = storage::get_round_by_index(&env, round)?;
+        if round_data.state != State::REFUNDING {
+            return Err(Error::InvalidState);
         }
 
-        // Transition to DRAWING state
-        storage::set_round_state(&env, round_num, State::DRAWING);
+        let tickets = storage::get_user_tickets(&env, round, &claimer);
+        if tickets == 0 {
+            return Err(Error::NothingToRefund);
+        }
+        if storage::is_refunded(&env, round, &claimer) {
+            return Err(Error::AlreadyRefunded);
+        }
 
-        // Call VRF contract to request random number
-        let vrf_args: Vec<Val> = (env.current_contract_address(),).into_val(&env);
+        // The pool is shared in proportion to what players paid. Each claim takes the growth of
+        // the share owed to everyone refunded so far, so the last one leaves no rounding dust.
+        let stats = storage::get_round_stats(&env, round)?;
+        let total_paid = stats.prize_pool.checked_add(stats.keeper_bounties).unwrap();
+        let share = |paid: i128| paid.checked_mul(stats.prize_pool).unwrap() / total_paid;
+        let refunded_before = storage::get_refunded_paid(&env, round);
+        let refunded_after = refunded_before
+            .checked_add(storage::get_user_paid(&env, round, &claimer))
+            .unwrap();
+        let amount = share(refunded_after) - share(refunded_before);
 
-        let random_value: u64 = env.invoke_contract(
-            &config.vrf_contract,
-            &Symbol::new(&env, "request_random"),
-            vrf_args,
-        );
+        // EFFECTS
+        storage::set_refunded(&env, round, &claimer);
+        storage::set_refunded_paid(&env, round, refunded_after);
+        let mut liabilities = storage::get_liabilities(&env);
+        liabilities.refunds = liabilities.refunds.checked_sub(amount).unwrap();
+        storage::set_liabilities(&env, &liabilities);
 
-        // Store the random value (VRF immediately calls back with fulfill_random)
-        storage::set_round_vrf_request(&env, round_num, random_value);
+        // INTERACTIONS
+        let config = storage::get_config(&env)?;
+        let token_client = Self::token_client(&env, config.underlying_token);
+        token_client.transfer(&env.current_contract_address(), &claimer, &amount);
 
-        // Emit event
-        events::emit_draw_requested(&env, round_num, random_value);
+        events::emit_refund_claimed(&env, round, &claimer, tickets, amount);
 
         storage::extend_instance_ttl(&env);
-        Ok(random_value)
+        Self::debug_assert_solvent(&env);
+        Ok(amount)
     }
 
-    /// VRF callback to fulfill randomness and select winner (AUTO-RESTART!)
-    pub fn fulfill_random(env: Env, vrf: Address, random_value: u64) -> Result<(), Error> {
+    /// Close sales on a commit-reveal round and open the reveal window (anyone can call once target met).
+    /// Returns the last ledger in which secrets can be revealed.
+    pub fn start_reveal(env: Env) -> Result<u32, Error> {
         let config = storage::get_config(&env)?;
+        if config.draw_mode != DrawMode::CommitReveal {
+            return Err(Error::WrongDrawMode);
+        }
 
-        // CRITICAL: Verify caller is VRF contract
-        if vrf != config.vrf_contract {
-            return Err(Error::UnauthorizedVRF);
+        let round = storage::get_current_round(&env)?;
+        let round_num = round.round;
+
+        Self::check_close_window(&env, &config, &round)?;
+
+        let stats = storage::get_round_stats(&env, round_num)?;
+        Self::check_draw_requirements(&config, &stats)?;
+
+        storage::set_round_state(&env, round_num, State::REVEAL);
+        Self::freeze_participants_root(&env, round_num);
+
+        let mut phase = storage::get_reveal_phase(&env, round_num);
+        phase.deadline_ledger = env.ledger().sequence().checked_add(config.reveal_window).unwrap();
+        storage::set_reveal_phase(&env, round_num, &phase);
+
+        events::emit_reveal_started(&env, round_num, phase.commitments, phase.deadline_ledger);
+
+        storage::extend_instance_ttl(&env);
+        Ok(phase.deadline_ledger)
+    }
+
+    /// Reveal the secret behind a participant's commitment. Anyone holding the secret can submit it,
+    /// the commitment binds it to the participant.
+    pub fn reveal(env: Env, participant: Address, secret: BytesN<32>) -> Result<(), Error> {
+        let round = storage::get_current_round(&env)?;
+        let round_num = round.round;
+
+        if round.state != State::REVEAL {
+            return Err(Error::InvalidState);
+        }
+
+        let mut phase = storage::get_reveal_phase(&env, round_num);
+        if env.ledger().sequence() > phase.deadline_ledger {
+            return Err(Error::RevealWindowClosed);
+        }
+
+        let committed = storage::get_commitment(&env, round_num, &participant).ok_or(Error::NoCommitment)?;
+        if storage::get_revealed(&env, round_num, &participant).is_some() {
+            return Err(Error::AlreadyRevealed);
+        }
+        if commitment::reveal_commitment(&env, round_num, &participant, &secret) != committed {
+            return Err(Error::InvalidReveal);
         }
 
+        storage::set_revealed(&env, round_num, &participant, &secret);
+        phase.reveals = phase.reveals.checked_add(1).unwrap();
+        phase.accumulator = commitment::accumulate_secret(&env, &phase.accumulator, &secret);
+        storage::set_reveal_phase(&env, round_num, &phase);
+
+        events::emit_secret_revealed(&env, round_num, &participant, &secret);
+
+        storage::extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Draw the winner of a commit-reveal round from the revealed secrets (anyone can call).
+    /// Callable once every commitment is revealed or the reveal window has passed; commitments
+    /// that were never revealed are skipped.
+    pub fn finalize_reveal(env: Env) -> Result<Address, Error> {
         let round = storage::get_current_round(&env)?;
         let round_num = round.round;
 
-        // Validate round state
-        if round.state != State::DRAWING {
+        if round.state != State::REVEAL {
             return Err(Error::InvalidState);
         }
 
-        // Get round stats to select winner
-        let stats = storage::get_round_stats(&env, round_num)?;
+        let phase = storage::get_reveal_phase(&env, round_num);
+        if phase.reveals < phase.commitments && env.ledger().sequence() <= phase.deadline_ledger {
+            return Err(Error::RevealWindowOpen);
+        }
+        // Without a revealed secret the seed would be public, the round can only be refunded
+        if phase.reveals == 0 {
+            return Err(Error::NoReveals);
+        }
 
-        // Select winning ticket: random_value % total_tickets
-        let winning_ticket = (random_value % stats.total_tickets as u64) as u32;
+        let seed = commitment::reveal_seed(&env, round_num, &phase.accumulator, phase.deadline_ledger);
+        storage::set_round_draw_seed(&env, round_num, &seed);
+        events::emit_reveal_finalized(&env, round_num, phase.commitments, phase.reveals, &seed);
 
-        // Find winner by iterating through participants' tickets
-        let winner = Self::find_winner_by_ticket(&env, round_num, winning_ticket, &stats)?;
+        Self::settle_round(&env, round_num, 0, &seed)
+    }
 
-        // Store winner record
-        let winner_record = WinnerRecord {
-            winner: winner.clone(),
-            round: round_num,
-            amount: stats.prize_pool,
-            claimed: false,
-        };
-        storage::set_winner_record(&env, round_num, &winner_record);
+    /// VRF callback to fulfill randomness and select winner (AUTO-RESTART!)
+    pub fn fulfill_random(
+        env: Env,
+        vrf: Address,
+        request_id: u64,
+        randomness: BytesN<32>,
+        keeper: Address,
+    ) -> Result<(), Error> {
+        let config = storage::get_config(&env)?;
+        let round = storage::get_current_round(&env)?;
+        let round_num = round.round;
 
-        // Mark round as COMPLETED
-        storage::set_round_state(&env, round_num, State::COMPLETED);
+        let draw = storage::get_draw_randomness(&env, round_num);
+        let position = draw
+            .as_ref()
+            .and_then(|draw| draw.contributions.iter().position(|contribution| contribution.provider == vrf));
 
-        // Emit winner selected event
-        events::emit_winner_selected(&env, round_num, &winner, stats.prize_pool);
+        // CRITICAL: Verify caller is a randomness provider
+        if position.is_none() && !config.vrf_providers.contains(&vrf) {
+            return Err(Error::UnauthorizedVRF);
+        }
+        vrf.require_auth();
 
-        // **AUTO-RESTART: Create next round**
-        storage::create_new_round(&env);
-        let next_round = storage::get_current_round_number(&env);
-        events::emit_round_started(&env, next_round);
+        // Validate round state
+        if round.state != State::DRAWING {
+            return Err(Error::InvalidState);
+        }
 
-        storage::extend_instance_ttl(&env);
+        // Providers added after the draw was requested weren't asked for this round
+        let (mut draw, idx) = match (draw, position) {
+            (Some(draw), Some(idx)) => (draw, idx as u32),
+            _ => return Err(Error::UnauthorizedVRF),
+        };
+        let mut contribution = draw.contributions.get(idx).unwrap();
+
+        // Only the request recorded for this round can settle it, never an old or replayed one
+        if contribution.request_id != request_id {
+            return Err(Error::StaleVRFRequest);
+        }
+        if contribution.random_value.is_some() {
+            return Err(Error::DuplicateContribution);
+        }
+
+        contribution.random_value = Some(randomness);
+        draw.contributions.set(idx, contribution);
+        storage::set_draw_randomness(&env, round_num, &draw);
+
+        let answered = draw
+            .contributions
+            .iter()
+            .filter(|contribution| contribution.random_value.is_some())
+            .count() as u32;
+        events::emit_contribution_received(&env, round_num, &vrf, request_id, answered, draw.threshold);
+
+        // The round stays DRAWING until enough providers answered. Earlier answers are already
+        // on-chain, so the one completing the threshold sees them before choosing its own.
+        if answered < draw.threshold {
+            storage::extend_instance_ttl(&env);
+            return Ok(());
+        }
+
+        Self::settle_draw(&env, round_num, Some(&keeper))?;
         Ok(())
     }
 
@@ -199,7 +447,8 @@ impl LuckyLedgersRaffle {
     pub fn claim_prize(env: Env, claimer: Address, round: u32) -> Result<i128, Error> {
         claimer.require_auth();
 
-        // CHECKS: Validate winner record exists
+        // CHECKS: Validate winner record exists (reading it also restores a full TTL
+        // to records that were archived and auto-restored)
         let winner_record = storage::get_winner_record(&env, round)
             .ok_or(Error::WinnerNotFound)?;
 
@@ -217,6 +466,7 @@ impl LuckyLedgersRaffle {
 
         // EFFECTS: Update state BEFORE external calls
         storage::update_winner_claimed(&env, round)?;
+        Self::release_unclaimed_prize(&env, prize_amount);
 
         // INTERACTIONS: Transfer tokens (external call LAST)
         let config = storage::get_config(&env)?;
@@ -227,46 +477,123 @@ impl LuckyLedgersRaffle {
         events::emit_prize_claimed(&env, round, &claimer, prize_amount);
 
         storage::extend_instance_ttl(&env);
+        Self::debug_assert_solvent(&env);
         Ok(prize_amount)
     }
 
-    /// Claim all unclaimed prizes for a user across all rounds
-    pub fn claim_all_prizes(env: Env, claimer: Address) -> Result<i128, Error> {
+    /// Claim up to `max_rounds` unclaimed prizes for a user, oldest first
+    pub fn claim_all_prizes(env: Env, claimer: Address, max_rounds: u32) -> Result<i128, Error> {
         claimer.require_auth();
 
-        let winning_rounds = storage::get_user_winning_rounds(&env, &claimer);
+        let unclaimed_rounds = storage::get_user_unclaimed_rounds(&env, &claimer);
         let mut total_claimed: i128 = 0;
-        let config = storage::get_config(&env)?;
-        let token_client = Self::token_client(&env, config.underlying_token);
 
-        for round in winning_rounds.iter() {
-            let winner_record = storage::get_winner_record(&env, round);
+        for round in unclaimed_rounds.iter().take(max_rounds as usize) {
+            let record = storage::get_winner_record(&env, round).ok_or(Error::WinnerNotFound)?;
+            let prize_amount = record.amount;
+
+            // EFFECTS: Update state BEFORE external calls (CEI pattern)
+            storage::update_winner_claimed(&env, round)?;
+            Self::release_unclaimed_prize(&env, prize_amount);
 
-            if let Some(record) = winner_record {
-                if !record.claimed && record.winner == claimer {
-                    let prize_amount = record.amount;
+            // Emit event
+            events::emit_prize_claimed(&env, round, &claimer, prize_amount);
+
+            total_claimed = total_claimed.checked_add(prize_amount).unwrap();
+        }
+
+        // INTERACTIONS: Transfer tokens (external call LAST)
+        if total_claimed > 0 {
+            let config = storage::get_config(&env)?;
+            let token_client = Self::token_client(&env, config.underlying_token);
+            token_client.transfer(&env.current_contract_address(), &claimer, &total_claimed);
+        }
 
-                    // EFFECTS: Update state BEFORE external calls (CEI pattern)
-                    storage::update_winner_claimed(&env, round)?;
+        storage::extend_instance_ttl(&env);
+        Self::debug_assert_solvent(&env);
+        Ok(total_claimed)
+    }
 
-                    // INTERACTIONS: Transfer tokens (external call LAST)
-                    token_client.transfer(&env.current_contract_address(), &claimer, &prize_amount);
+    /// Extend the TTL of a round's entries so they can't be archived, in chunks of up to
+    /// `max_entries` participants and ticket entries from `start` (anyone can call).
+    /// Returns how many are left after this chunk.
+    pub fn bump_round(env: Env, round: u32, start: u32, max_entries: u32) -> Result<u32, Error> {
+        let remaining = storage::bump_round(&env, round, start, max_entries)?;
+        storage::extend_instance_ttl(&env);
+        Ok(remaining)
+    }
 
-                    // Emit event
-                    events::emit_prize_claimed(&env, round, &claimer, prize_amount);
+    /// Delete per-participant data of a finalized round in chunks (anyone can call).
+    /// A summary keeping the round's participants root is recorded before anything is deleted.
+    /// Returns the number of participants still left to prune.
+    pub fn prune_round(env: Env, round: u32, max_entries: u32) -> Result<u32, Error> {
+        let round_data = storage::get_round_by_index(&env, round)?;
+        if round_data.state != State::COMPLETED {
+            return Err(Error::InvalidState);
+        }
 
-                    total_claimed = total_claimed.checked_add(prize_amount).unwrap();
+        // Record the summary on the first call, the root was frozen before the draw
+        let mut summary = match storage::get_round_summary(&env, round) {
+            Some(summary) => summary,
+            None => {
+                let stats = storage::get_round_stats(&env, round)?;
+                RoundSummary {
+                    participant_count: stats.total_participants,
+                    participants_root: Self::get_participants_root(env.clone(), round)?,
+                    pruned_participants: 0,
+                    entry_count: storage::get_participants_tree(&env, round).leaf_count,
+                    pruned_entries: 0,
                 }
             }
+        };
+
+        let mut pruned: u32 = 0;
+        let mut bucket_idx = summary.pruned_participants / storage::BUCKET_SIZE;
+        let mut bucket: Option<ParticipantBucket> = None;
+
+        while pruned < max_entries && summary.pruned_participants < summary.participant_count {
+            let current = bucket.get_or_insert_with(|| storage::get_participant_bucket(&env, round, bucket_idx));
+            let offset = summary.pruned_participants % storage::BUCKET_SIZE;
+            if let Some(participant) = current.participants.get(offset) {
+                storage::remove_user_tickets(&env, round, &participant);
+                storage::remove_user_paid(&env, round, &participant);
+                storage::remove_commit_reveal(&env, round, &participant);
+            }
+
+            summary.pruned_participants += 1;
+            pruned += 1;
+
+            // Drop the bucket once every participant in it has been pruned
+            if summary.pruned_participants % storage::BUCKET_SIZE == 0
+                || summary.pruned_participants == summary.participant_count
+            {
+                storage::remove_participant_bucket(&env, round, bucket_idx);
+                bucket_idx += 1;
+                bucket = None;
+            }
+        }
+
+        // Then the ticket entries, the frozen root still proves them
+        while pruned < max_entries && summary.pruned_entries < summary.entry_count {
+            storage::remove_ticket_entry(&env, round, summary.pruned_entries);
+            summary.pruned_entries += 1;
+            pruned += 1;
         }
 
+        storage::set_round_summary(&env, round, &summary);
+
+        let remaining = (summary.participant_count - summary.pruned_participants)
+            + (summary.entry_count - summary.pruned_entries);
+        events::emit_round_pruned(&env, round, pruned, remaining);
+
         storage::extend_instance_ttl(&env);
-        Ok(total_claimed)
+        Ok(remaining)
     }
 
     /// Upgrade the contract to new wasm. Only callable by admin.
     pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
         Self::require_admin(env);
+        events::emit_contract_upgraded(env, &new_wasm_hash);
         env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
@@ -277,9 +604,123 @@ impl LuckyLedgersRaffle {
 
     /// Set a new admin. Only callable by admin.
     pub fn set_new_admin(env: &Env, admin: Address) {
-        Self::require_admin(env);
+        let previous_admin = Self::require_admin(env);
         storage::extend_instance_ttl(env);
         storage::set_admin(env, &admin);
+        events::emit_admin_changed(env, &previous_admin, &admin);
+    }
+
+    /// Replace the raffle configuration before the current round sells any ticket. The
+    /// underlying token can't change. Only callable by admin.
+    pub fn update_config(env: &Env, config: Config) -> Result<(), Error> {
+        Self::require_admin(env);
+
+        // Liabilities and refunds are kept in the underlying token, it can never change
+        let current = storage::get_config(env)?;
+        if config.underlying_token != current.underlying_token {
+            return Err(Error::InvalidConfig);
+        }
+        // Changes apply from a round nobody has bought into yet
+        let round = storage::get_current_round(env)?;
+        if round.state != State::OPEN || storage::get_round_stats(env, round.round)?.total_tickets > 0 {
+            return Err(Error::ConfigLocked);
+        }
+
+        if config.ticket_price <= 0 || config.target_tickets == 0 || config.max_tickets_per_participant == 0 {
+            return Err(Error::InvalidConfig);
+        }
+        if config.draw_mode == DrawMode::CommitReveal && (config.reveal_window == 0 || config.auto_draw) {
+            return Err(Error::InvalidConfig);
+        }
+        if config.draw_timeout == 0 || config.max_draw_attempts == 0 {
+            return Err(Error::InvalidConfig);
+        }
+        if config.min_participants == 0 {
+            return Err(Error::InvalidConfig);
+        }
+        // A sold-out round must always be drawable: past the target, and with enough buyers
+        // given each holds at most max_tickets_per_participant
+        if config.max_total_tickets > 0
+            && (config.max_total_tickets < config.target_tickets
+                || config.max_total_tickets.div_ceil(config.max_tickets_per_participant) < config.min_participants)
+        {
+            return Err(Error::InvalidConfig);
+        }
+        if !Self::valid_price_tiers(&config) || !Self::valid_price_curve(&config) {
+            return Err(Error::InvalidConfig);
+        }
+        if !Self::valid_bounties(&config) {
+            return Err(Error::InvalidConfig);
+        }
+        if config.vrf_threshold == 0 || config.vrf_threshold > config.vrf_providers.len() {
+            return Err(Error::InvalidConfig);
+        }
+        // A synchronous answer is known when the entry is simulated, the entrant starting an
+        // auto draw could see the winner before submitting
+        if config.auto_draw && config.provider_kind == ProviderKind::Synchronous {
+            return Err(Error::InvalidConfig);
+        }
+        for (idx, provider) in config.vrf_providers.iter().enumerate() {
+            if config.vrf_providers.first_index_of(&provider) != Some(idx as u32) {
+                return Err(Error::InvalidConfig);
+            }
+        }
+
+        storage::set_config(env, config.clone());
+        events::emit_config_updated(env, &config);
+        storage::extend_instance_ttl(env);
+        Ok(())
+    }
+
+    /// Get current treasurer (the admin unless one was set)
+    pub fn treasurer(env: &Env) -> Result<Address, Error> {
+        storage::get_treasurer(env)
+    }
+
+    /// Set a new treasurer. Only callable by admin.
+    pub fn set_treasurer(env: &Env, treasurer: Address) {
+        Self::require_admin(env);
+        storage::extend_instance_ttl(env);
+        storage::set_treasurer(env, &treasurer);
+        events::emit_treasurer_changed(env, &treasurer);
+    }
+
+    /// Recover tokens sent directly to the contract. Only callable by treasurer.
+    /// For the underlying token only the surplus above prize liabilities can be recovered.
+    pub fn recover_tokens(env: Env, token: Address, to: Address, amount: i128) -> Result<(), Error> {
+        let treasurer = storage::get_treasurer(&env)?;
+        treasurer.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config = storage::get_config(&env)?;
+        if token == config.underlying_token {
+            let audit = Self::compute_audit(&env)?;
+            if amount > audit.surplus {
+                return Err(Error::InsufficientSurplus);
+            }
+        }
+
+        let token_client = Self::token_client(&env, token.clone());
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+
+        events::emit_tokens_recovered(&env, &token, &to, amount);
+
+        storage::extend_instance_ttl(&env);
+        Self::debug_assert_solvent(&env);
+        Ok(())
+    }
+
+    /// Start a new leaderboard season, archiving the previous season's boards. Only callable by admin.
+    pub fn start_new_season(env: &Env) -> u32 {
+        Self::require_admin(env);
+        let season = storage::get_current_season(env).checked_add(1).unwrap();
+        storage::set_current_season(env, season);
+        events::emit_season_started(env, season);
+        storage::extend_instance_ttl(env);
+        season
     }
 
     // ========== VIEW/QUERY FUNCTIONS ==========
@@ -299,6 +740,14 @@ impl LuckyLedgersRaffle {
         storage::get_round_stats(&env, round)
     }
 
+    /// Quote what `enter(user, num_tickets)` would buy in the current round, and at what cost
+    pub fn quote_entry(env: Env, user: Address, num_tickets: u32) -> Result<EntryQuote, Error> {
+        let config = storage::get_config(&env)?;
+        let round = storage::get_current_round(&env)?;
+        let stats = storage::get_round_stats(&env, round.round)?;
+        Self::quote(&env, &config, &round, &stats, &user, num_tickets)
+    }
+
     /// Get user's ticket count for a round
     pub fn get_user_tickets(env: Env, round: u32, user: Address) -> u32 {
         storage::get_user_tickets(&env, round, &user)
@@ -309,39 +758,164 @@ impl LuckyLedgersRaffle {
         storage::get_all_participants(&env, round)
     }
 
+    /// Get the participant summary recorded when a round was pruned
+    pub fn get_round_summary(env: Env, round: u32) -> Option<RoundSummary> {
+        storage::get_round_summary(&env, round)
+    }
+
+    /// Get the commit-reveal progress of a round
+    pub fn get_reveal_phase(env: Env, round: u32) -> RevealPhase {
+        storage::get_reveal_phase(&env, round)
+    }
+
+    /// Get a participant's commitment for a round
+    pub fn get_commitment(env: Env, round: u32, user: Address) -> Option<BytesN<32>> {
+        storage::get_commitment(&env, round, &user)
+    }
+
+    /// Get the providers asked for a round's randomness and which of them have answered
+    pub fn get_draw_randomness(env: Env, round: u32) -> Option<DrawRandomness> {
+        storage::get_draw_randomness(&env, round)
+    }
+
+    /// Get the record of a round's draw
+    pub fn get_draw_record(env: Env, round: u32) -> Option<DrawRecord> {
+        storage::get_draw_record(&env, round)
+    }
+
+    /// Recompute a completed round's winner from its draw record and ticket entries, checking
+    /// the entries' tree still has the root the draw was made against.
+    /// Returns whether it matches what was recorded. Fails once the round was pruned.
+    pub fn verify_draw(env: Env, round: u32) -> Result<bool, Error> {
+        let record = storage::get_draw_record(&env, round).ok_or(Error::DrawRecordNotFound)?;
+        if storage::get_round_summary(&env, round).is_some_and(|summary| summary.pruned_participants > 0) {
+            return Err(Error::RoundPruned);
+        }
+
+        let stats = storage::get_round_stats(&env, round)?;
+        let winning_ticket = commitment::draw_index(&env, &record.seed, commitment::WINNER_DRAW, record.total_tickets);
+        let winner_record = storage::get_winner_record(&env, round).ok_or(Error::WinnerNotFound)?;
+
+        // A list that changed since the draw may not even cover the ticket
+        let winner = Self::find_winner_by_ticket(&env, round, winning_ticket, &stats).ok();
+
+        let round_data = storage::get_round_by_index(&env, round)?;
+        let tree_root = merkle::root(&env, &storage::get_participants_tree(&env, round));
+
+        Ok(stats.total_tickets == record.total_tickets
+            && round_data.participants_root.as_ref() == Some(&record.participants_root)
+            && tree_root == record.participants_root
+            && winning_ticket == record.winning_ticket
+            && winner.as_ref() == Some(&record.winner)
+            && winner_record.winner == record.winner)
+    }
+
+    /// Winner a round's current participant list would get from `seed`, as the draw picks it
+    pub fn preview_winner(env: Env, round: u32, seed: BytesN<32>) -> Result<Address, Error> {
+        let stats = storage::get_round_stats(&env, round)?;
+        if stats.total_tickets == 0 {
+            return Err(Error::WinnerNotFound);
+        }
+
+        let winning_ticket = commitment::draw_index(&env, &seed, commitment::WINNER_DRAW, stats.total_tickets);
+        Self::find_winner_by_ticket(&env, round, winning_ticket, &stats)
+    }
+
+    /// Merkle root over a round's ticket entries: the one frozen when sales closed, or the
+    /// running root while the round is still open
+    pub fn get_participants_root(env: Env, round: u32) -> Result<BytesN<32>, Error> {
+        let round_data = storage::get_round_by_index(&env, round)?;
+        Ok(round_data
+            .participants_root
+            .unwrap_or_else(|| merkle::root(&env, &storage::get_participants_tree(&env, round))))
+    }
+
+    /// Get a ticket entry of a round by its index, until the round is pruned
+    pub fn get_ticket_entry(env: Env, round: u32, index: u32) -> Option<TicketEntry> {
+        storage::get_ticket_entry(&env, round, index)
+    }
+
+    /// Check an inclusion proof of `entry` at `index` against the round's participants root.
+    /// `proof` holds the sibling hashes from the leaf up, see `merkle` for the hashing.
+    pub fn verify_inclusion(
+        env: Env,
+        round: u32,
+        entry: TicketEntry,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<bool, Error> {
+        let root = Self::get_participants_root(env.clone(), round)?;
+        Ok(merkle::verify_proof(&env, &root, &merkle::leaf_hash(&env, &entry), index, &proof))
+    }
+
     /// Get winner record for a round
     pub fn get_winner(env: Env, round: u32) -> Option<WinnerRecord> {
         storage::get_winner_record(&env, round)
     }
 
-    /// Get all rounds where a user won
-    pub fn get_user_winning_rounds(env: Env, user: Address) -> Vec<u32> {
-        storage::get_user_winning_rounds(&env, &user)
+    /// Get a page of the rounds where a user won, oldest first
+    pub fn get_user_winning_rounds(env: Env, user: Address, start: u32, limit: u32) -> Vec<u32> {
+        let winning_rounds = storage::get_user_winning_rounds(&env, &user);
+        Self::paginate(&env, &winning_rounds, start, limit)
+    }
+
+    /// Get a page of a user's winner records, oldest first
+    pub fn get_user_win_history(env: Env, user: Address, start: u32, limit: u32) -> Vec<WinnerRecord> {
+        let winning_rounds = storage::get_user_winning_rounds(&env, &user);
+        let mut history = Vec::new(&env);
+
+        for round in Self::paginate(&env, &winning_rounds, start, limit).iter() {
+            if let Some(record) = storage::get_winner_record(&env, round) {
+                history.push_back(record);
+            }
+        }
+
+        history
     }
 
     /// Get all unclaimed prizes for a user
     pub fn get_unclaimed_prizes(env: Env, user: Address) -> Vec<WinnerRecord> {
-        let winning_rounds = storage::get_user_winning_rounds(&env, &user);
+        let unclaimed_rounds = storage::get_user_unclaimed_rounds(&env, &user);
         let mut unclaimed = Vec::new(&env);
 
-        for round in winning_rounds.iter() {
+        for round in unclaimed_rounds.iter() {
             if let Some(record) = storage::get_winner_record(&env, round) {
-                if !record.claimed {
-                    unclaimed.push_back(record);
-                }
+                unclaimed.push_back(record);
             }
         }
 
         unclaimed
     }
 
+    /// Get current leaderboard season
+    pub fn get_current_season(env: Env) -> u32 {
+        storage::get_current_season(&env)
+    }
+
+    /// Get a leaderboard for a season, or the all-time leaderboard when `season` is None
+    pub fn get_leaderboard(env: Env, kind: LeaderboardKind, season: Option<u32>) -> Vec<LeaderboardEntry> {
+        let season = season.unwrap_or(storage::ALL_TIME_SEASON);
+        storage::get_leaderboard(&env, kind, season)
+    }
+
     /// Check if current round is ready to draw
     pub fn is_ready_to_draw(env: Env) -> Result<bool, Error> {
         let config = storage::get_config(&env)?;
         let round = storage::get_current_round(&env)?;
         let stats = storage::get_round_stats(&env, round.round)?;
 
-        Ok(round.state == State::OPEN && stats.total_tickets >= config.target_tickets)
+        Ok(Self::check_close_window(&env, &config, &round).is_ok()
+            && Self::check_draw_requirements(&config, &stats).is_ok())
+    }
+
+    /// Compare the contract's token balance against what it owes players
+    pub fn audit(env: Env) -> Result<Audit, Error> {
+        Self::compute_audit(&env)
+    }
+
+    /// Get the version carried in the first topic of every raffle event
+    pub fn event_schema_version(_env: Env) -> u32 {
+        events::EVENT_SCHEMA_VERSION
     }
 
     /// Get contract configuration
@@ -351,41 +925,578 @@ impl LuckyLedgersRaffle {
 
     // ========== PRIVATE HELPER FUNCTIONS ==========
 
+    /// Pick the winning ticket of a round from `randomness`, record the prize and start the next round
+    fn settle_round(env: &Env, round_num: u32, request_id: u64, randomness: &BytesN<32>) -> Result<Address, Error> {
+        // Get round stats to select winner
+        let stats = storage::get_round_stats(env, round_num)?;
+
+        // Select winning ticket: the round's first draw, uniform over all tickets
+        let winning_ticket = commitment::draw_index(env, randomness, commitment::WINNER_DRAW, stats.total_tickets);
+
+        // Find winner by iterating through participants' tickets
+        let winner = Self::find_winner_by_ticket(env, round_num, winning_ticket, &stats)?;
+
+        // Keep everything needed to recompute the winner later
+        let draw_record = DrawRecord {
+            seed: randomness.clone(),
+            winning_ticket,
+            total_tickets: stats.total_tickets,
+            participants_root: Self::get_participants_root(env.clone(), round_num)?,
+            vrf_request_id: request_id,
+            winner: winner.clone(),
+        };
+        storage::set_draw_record(env, round_num, &draw_record);
+
+        // Store winner record
+        let winner_record = WinnerRecord {
+            winner: winner.clone(),
+            round: round_num,
+            amount: stats.prize_pool,
+            claimed: false,
+            vrf_request_id: request_id,
+        };
+        storage::set_winner_record(env, round_num, &winner_record);
+        storage::add_winning_round(env, &winner, round_num);
+
+        // Move the pool from the open round to the winner's unclaimed prize
+        let mut liabilities = storage::get_liabilities(env);
+        liabilities.open_pool = liabilities.open_pool.checked_sub(stats.prize_pool).unwrap();
+        liabilities.unclaimed_prizes = liabilities.unclaimed_prizes.checked_add(stats.prize_pool).unwrap();
+        storage::set_liabilities(env, &liabilities);
+
+        // Update winner leaderboards
+        leaderboard::record(env, LeaderboardKind::TopWinners, &winner, stats.prize_pool);
+
+        // Mark round as COMPLETED
+        storage::set_round_state(env, round_num, State::COMPLETED);
+
+        // Emit winner selected event
+        events::emit_winner_selected(
+            env,
+            round_num,
+            &winner,
+            stats.prize_pool,
+            winning_ticket,
+            randomness,
+            stats.total_tickets,
+        );
+
+        // **AUTO-RESTART: Create next round**
+        storage::create_new_round(env);
+        let next_round = storage::get_current_round_number(env);
+        events::emit_round_started(env, next_round);
+
+        storage::extend_instance_ttl(env);
+        Self::debug_assert_solvent(env);
+        Ok(winner)
+    }
+
+    /// Close sales on an open round, request its randomness and pay the keeper's draw bounty, if
+    /// there is a keeper. Every call that can fail runs before the first write, for synchronous
+    /// providers that includes finding the winner their answers pick. Returns the first
+    /// provider's request ID.
+    fn start_draw(
+        env: &Env,
+        config: &Config,
+        round_num: u32,
+        total_tickets: u32,
+        keeper: Option<&Address>,
+    ) -> Result<u64, Error> {
+        let (draw, seed) = Self::request_randomness(env, config, round_num, total_tickets)?;
+        if config.provider_kind == ProviderKind::Synchronous {
+            Self::check_settles(env, round_num, &draw)?;
+        }
+
+        // Transition to DRAWING state, sales are closed
+        let request_id = Self::record_draw(env, round_num, &draw, &seed);
+        storage::set_round_state(env, round_num, State::DRAWING);
+        Self::freeze_participants_root(env, round_num);
+
+        events::emit_draw_requested(env, round_num, request_id, total_tickets, &seed);
+        if let Some(keeper) = keeper {
+            Self::pay_keeper(env, config, round_num, keeper, &config.draw_bounty, KeeperTask::Draw)?;
+        }
+
+        // Synchronous providers already answered, the round settles in this call and the
+        // keeper also triggered the fulfillment
+        if config.provider_kind == ProviderKind::Synchronous {
+            Self::settle_draw(env, round_num, keeper)?;
+        }
+        Ok(request_id)
+    }
+
+    /// Commit to the round as it stands and request randomness from every provider. Callback
+    /// providers fulfill it later, synchronous ones answer right away with request ID 0.
+    /// Nothing is stored, returns the contributions and the committed seed.
+    fn request_randomness(
+        env: &Env,
+        config: &Config,
+        round_num: u32,
+        total_tickets: u32,
+    ) -> Result<(DrawRandomness, BytesN<32>), Error> {
+        let seed = commitment::draw_seed(env, round_num, total_tickets);
+        let raffle = env.current_contract_address();
+
+        // Providers are called before anything is stored, a failing one leaves the round untouched
+        let mut contributions = Vec::new(env);
+        for provider in config.vrf_providers.iter() {
+            let contribution = match config.provider_kind {
+                ProviderKind::Callback => {
+                    let client = RandomnessProviderClient::new(env, &provider);
+                    let Ok(Ok(request_id)) = client.try_request_random(&raffle, &seed) else {
+                        return Err(Error::VRFRequestFailed);
+                    };
+                    Contribution {
+                        request_id,
+                        provider,
+                        random_value: None,
+                    }
+                }
+                ProviderKind::Synchronous => {
+                    let client = SyncRandomnessProviderClient::new(env, &provider);
+                    let Ok(Ok(randomness)) = client.try_random_value(&raffle, &seed) else {
+                        return Err(Error::VRFRequestFailed);
+                    };
+                    Contribution {
+                        request_id: 0,
+                        provider,
+                        random_value: Some(randomness),
+                    }
+                }
+            };
+            contributions.push_back(contribution);
+        }
+
+        let draw = DrawRandomness {
+            threshold: config.vrf_threshold,
+            contributions,
+        };
+        Ok((draw, seed))
+    }
+
+    /// Store a draw's contributions and seed, replacing any earlier attempt for the round whose
+    /// requests can no longer answer. Returns the first provider's request ID.
+    fn record_draw(env: &Env, round_num: u32, draw: &DrawRandomness, seed: &BytesN<32>) -> u64 {
+        storage::set_draw_randomness(env, round_num, draw);
+
+        let request_id = draw.contributions.get(0).unwrap().request_id;
+        storage::set_round_vrf_request(env, round_num, request_id, seed);
+        request_id
+    }
+
+    /// Fail the way settling `draw` would, so a synchronous draw can check it before writing
+    fn check_settles(env: &Env, round_num: u32, draw: &DrawRandomness) -> Result<(), Error> {
+        let stats = storage::get_round_stats(env, round_num)?;
+        let randomness = commitment::contributions_seed(env, &draw.contributions);
+        let winning_ticket = commitment::draw_index(env, &randomness, commitment::WINNER_DRAW, stats.total_tickets);
+        Self::find_winner_by_ticket(env, round_num, winning_ticket, &stats).map(|_| ())
+    }
+
+    /// Settle a drawing round from the contributions recorded for it
+    fn settle_draw(env: &Env, round_num: u32, keeper: Option<&Address>) -> Result<Address, Error> {
+        let draw = storage::get_draw_randomness(env, round_num).ok_or(Error::InvalidState)?;
+
+        // The fulfillment bounty comes out of the pool before the prize is fixed
+        if let Some(keeper) = keeper {
+            let config = storage::get_config(env)?;
+            Self::pay_keeper(env, &config, round_num, keeper, &config.fulfill_bounty, KeeperTask::Fulfillment)?;
+        }
+
+        // Records carry the first provider's request ID, the one the round was marked with
+        let request_id = draw.contributions.get(0).ok_or(Error::InvalidState)?.request_id;
+        // The answers received are hashed together in provider order, however many there are
+        let randomness = commitment::contributions_seed(env, &draw.contributions);
+        Self::settle_round(env, round_num, request_id, &randomness)
+    }
+
+    /// What buying `num_tickets` gets `user` in the round after the wallet and round caps.
+    /// Fails the way an entry would when the round isn't selling.
+    fn quote(
+        env: &Env,
+        config: &Config,
+        round: &Round,
+        stats: &RoundStats,
+        user: &Address,
+        num_tickets: u32,
+    ) -> Result<EntryQuote, Error> {
+        // Validate round state
+        match round.state {
+            State::OPEN => {}
+            State::CLOSED if config.max_total_tickets > 0 && stats.total_tickets >= config.max_total_tickets => {
+                return Err(Error::SoldOut);
+            }
+            State::CLOSED => return Err(Error::SalesClosed),
+            _ => return Err(Error::RoundNotOpen),
+        }
+
+        let previous_tickets = storage::get_user_tickets(env, round.round, user);
+
+        // Auto-cap: ensure user doesn't exceed max_tickets_per_participant
+        let remaining_allowance = config.max_tickets_per_participant.saturating_sub(previous_tickets);
+        let mut tickets = num_tickets.min(remaining_allowance);
+
+        // Hard cap: the purchase reaching max_total_tickets is partially filled
+        if config.max_total_tickets > 0 {
+            let remaining_tickets = config.max_total_tickets.saturating_sub(stats.total_tickets);
+            if remaining_tickets == 0 {
+                return Err(Error::SoldOut);
+            }
+            tickets = tickets.min(remaining_tickets);
+        }
+
+        let user_tickets = previous_tickets + tickets;
+        let total_tickets = stats.total_tickets.checked_add(tickets).unwrap();
+        let odds_bps = match total_tickets {
+            0 => 0,
+            total => (user_tickets as u64 * storage::BASIS_POINTS as u64 / total as u64) as u32,
+        };
+
+        Ok(EntryQuote {
+            tickets,
+            cost: pricing::ticket_cost(config, stats.total_tickets, tickets),
+            marginal_price: pricing::marginal_price(config, stats.total_tickets),
+            user_tickets,
+            total_tickets,
+            odds_bps,
+        })
+    }
+
+    /// End sales on an open round, freezing its entries. Returns the first ledger a draw can be
+    /// requested on.
+    fn close_round(env: &Env, config: &Config, round_num: u32, total_tickets: u32) -> u32 {
+        storage::set_round_closed(env, round_num);
+        Self::freeze_participants_root(env, round_num);
+
+        let draw_ledger = env.ledger().sequence().saturating_add(config.close_window);
+        events::emit_sales_closed(env, round_num, total_tickets, draw_ledger);
+        draw_ledger
+    }
+
+    /// A round is drawn from CLOSED once close_window ledgers passed, or straight from OPEN
+    /// when there is no window
+    fn check_close_window(env: &Env, config: &Config, round: &Round) -> Result<(), Error> {
+        match round.state {
+            State::OPEN if config.close_window == 0 => Ok(()),
+            State::OPEN => Err(Error::SalesOpen),
+            State::CLOSED if env.ledger().sequence() >= round.closed_ledger.saturating_add(config.close_window) => Ok(()),
+            State::CLOSED => Err(Error::CloseWindowOpen),
+            _ => Err(Error::InvalidState),
+        }
+    }
+
+    /// A round can be drawn once it sold the target tickets to enough distinct participants
+    fn check_draw_requirements(config: &Config, stats: &RoundStats) -> Result<(), Error> {
+        if stats.total_tickets < config.target_tickets {
+            return Err(Error::TargetNotMet);
+        }
+        if stats.total_participants < config.min_participants {
+            return Err(Error::NotEnoughParticipants);
+        }
+        Ok(())
+    }
+
+    /// Whether the latest VRF request for a drawing round has gone unanswered past the timeout
+    fn draw_timed_out(env: &Env, config: &Config, round: &Round) -> bool {
+        env.ledger().sequence() > round.draw_ledger.saturating_add(config.draw_timeout)
+    }
+
+    /// Buy tickets in the current round for a caller that has already authorized.
+    /// With `max_cost` the purchase is strict: all `num_tickets` within budget, or an error.
+    fn enter_round(env: &Env, caller: &Address, num_tickets: u32, max_cost: Option<i128>) -> Result<u32, Error> {
+        let config = storage::get_config(env)?;
+        let round = storage::get_current_round(env)?;
+        let round_num = round.round;
+
+        // Get current round stats
+        let mut stats = storage::get_round_stats(env, round_num)?;
+        let quote = Self::quote(env, &config, &round, &stats, caller, num_tickets)?;
+
+        if let Some(max_cost) = max_cost {
+            if num_tickets == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            if quote.tickets < num_tickets {
+                return Err(Error::InsufficientTickets);
+            }
+            if quote.cost > max_cost {
+                return Err(Error::MaxCostExceeded);
+            }
+        }
+
+        let was_drawable = Self::check_draw_requirements(&config, &stats).is_ok();
+
+        // Check if this is user's first entry (new participant)
+        let tickets_to_buy = quote.tickets;
+        let previous_tickets = quote.user_tickets - tickets_to_buy;
+        let is_new_participant = previous_tickets == 0;
+
+        // If user is already at cap or tries to buy 0, return current total
+        if tickets_to_buy == 0 {
+            return Ok(previous_tickets);
+        }
+
+        // Payment for the tickets actually being purchased
+        let amount = quote.cost;
+
+        // Transfer tokens from caller to contract
+        let token_client = Self::token_client(env, config.underlying_token.clone());
+        #[allow(clippy::needless_borrows_for_generic_args)]
+        token_client.transfer(caller, &env.current_contract_address(), &amount);
+
+        // Here the transfered amount could be deposited into Blend or DeFindex
+
+        // Add tickets for user
+        storage::add_user_tickets(env, round_num, caller, tickets_to_buy);
+        storage::add_user_paid(env, round_num, caller, amount);
+        let user_total_tickets = previous_tickets + tickets_to_buy;
+
+        // If new participant, add to participant bucket
+        if is_new_participant {
+            storage::add_participant(env, round_num, caller);
+            // Re-fetch stats after add_participant updates it
+            stats = storage::get_round_stats(env, round_num)?;
+        }
+
+        // Update round stats
+        let previous_total_tickets = stats.total_tickets;
+        stats.total_tickets = stats.total_tickets.checked_add(tickets_to_buy).unwrap();
+        stats.prize_pool = stats.prize_pool.checked_add(amount).unwrap();
+        storage::set_round_stats(env, round_num, &stats);
+
+        // Record the purchase as the next ticket range and fold it into the participants tree
+        let entry = TicketEntry {
+            participant: caller.clone(),
+            first_ticket: previous_total_tickets,
+            tickets: tickets_to_buy,
+        };
+        let mut tree = storage::get_participants_tree(env, round_num);
+        let entry_index = tree.leaf_count;
+        storage::set_ticket_entry(env, round_num, entry_index, &entry);
+        merkle::append(env, &mut tree, &merkle::leaf_hash(env, &entry));
+        storage::set_participants_tree(env, round_num, &tree);
+
+        // Track the open pool as owed to players
+        let mut liabilities = storage::get_liabilities(env);
+        liabilities.open_pool = liabilities.open_pool.checked_add(amount).unwrap();
+        storage::set_liabilities(env, &liabilities);
+
+        // Update ticket buyer leaderboards
+        leaderboard::record(env, LeaderboardKind::TopBuyers, caller, tickets_to_buy as i128);
+
+        // Emit event
+        let marginal_price = pricing::marginal_price(&config, stats.total_tickets);
+        events::emit_player_entered(env, round_num, &entry, entry_index, amount, marginal_price);
+
+        let sold_out = config.max_total_tickets > 0 && stats.total_tickets == config.max_total_tickets;
+        if sold_out {
+            events::emit_sold_out(env, round_num, stats.total_tickets);
+        }
+
+        // Announce once, on the entry that meets the target and participant minimum
+        let became_drawable = !was_drawable && Self::check_draw_requirements(&config, &stats).is_ok();
+        if became_drawable {
+            events::emit_ready_to_draw(env, round_num, stats.total_tickets);
+        }
+
+        // Sales end when the round sells out, or on the entry an auto-draw waits a close window after
+        let auto_draw = became_drawable && config.auto_draw && config.draw_mode == DrawMode::Vrf;
+        if sold_out || (auto_draw && config.close_window > 0) {
+            Self::close_round(env, &config, round_num, stats.total_tickets);
+        }
+
+        // A failed auto-draw keeps the entry, the round waits for request_draw instead. The
+        // entrant did no keeper work and earns no draw bounty.
+        if auto_draw
+            && config.close_window == 0
+            && Self::start_draw(env, &config, round_num, stats.total_tickets, None).is_err()
+        {
+            events::emit_auto_draw_failed(env, round_num);
+        }
+
+        storage::extend_instance_ttl(env);
+        Self::debug_assert_solvent(env);
+        Ok(user_total_tickets)
+    }
+
     /// Private helper function to require auth from the admin
-    fn require_admin(env: &Env) {
+    fn require_admin(env: &Env) -> Address {
         let admin = storage::get_admin(env).unwrap();
         admin.require_auth();
+        admin
+    }
+
+    fn compute_audit(env: &Env) -> Result<Audit, Error> {
+        let config = storage::get_config(env)?;
+        let balance = Self::token_client(env, config.underlying_token)
+            .balance(&env.current_contract_address());
+
+        let tracked = storage::get_liabilities(env);
+        let liabilities = tracked
+            .unclaimed_prizes
+            .checked_add(tracked.open_pool)
+            .and_then(|total| total.checked_add(tracked.refunds))
+            .unwrap();
+
+        Ok(Audit {
+            balance,
+            liabilities,
+            surplus: balance.checked_sub(liabilities).unwrap(),
+        })
+    }
+
+    /// Reduce unclaimed prize liabilities once a prize is paid out
+    fn release_unclaimed_prize(env: &Env, amount: i128) {
+        let mut liabilities = storage::get_liabilities(env);
+        liabilities.unclaimed_prizes = liabilities.unclaimed_prizes.checked_sub(amount).unwrap();
+        storage::set_liabilities(env, &liabilities);
+    }
+
+    /// Debug builds check the balance still covers every liability after a state change
+    fn debug_assert_solvent(env: &Env) {
+        if cfg!(debug_assertions) {
+            let audit = Self::compute_audit(env).unwrap();
+            debug_assert!(audit.surplus >= 0, "raffle is insolvent");
+        }
+    }
+
+    /// Slice `start..start + limit` out of a list, capping the page size
+    fn paginate(env: &Env, items: &Vec<u32>, start: u32, limit: u32) -> Vec<u32> {
+        let end = start
+            .saturating_add(limit.min(storage::MAX_PAGE_SIZE))
+            .min(items.len());
+        if start >= end {
+            return Vec::new(env);
+        }
+        items.slice(start..end)
     }
 
     fn token_client<'a>(env: &Env, contract_id: Address) -> soroban_sdk::token::TokenClient<'a> {
         soroban_sdk::token::TokenClient::new(env, &contract_id)
     }
 
-    /// Find winner by iterating through participants' ticket allocations
+    /// Pay a keeper bounty out of the round's pool. Returns the amount paid.
+    fn pay_keeper(
+        env: &Env,
+        config: &Config,
+        round_num: u32,
+        keeper: &Address,
+        bounty: &KeeperBounty,
+        task: KeeperTask,
+    ) -> Result<i128, Error> {
+        let mut stats = storage::get_round_stats(env, round_num)?;
+        let amount = match bounty {
+            KeeperBounty::None => 0,
+            KeeperBounty::Fixed(amount) => (*amount).min(stats.prize_pool),
+            KeeperBounty::BasisPoints(bps) => {
+                stats.prize_pool.checked_mul(*bps as i128).unwrap() / storage::BASIS_POINTS as i128
+            }
+        };
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        // EFFECTS
+        stats.prize_pool = stats.prize_pool.checked_sub(amount).unwrap();
+        stats.keeper_bounties = stats.keeper_bounties.checked_add(amount).unwrap();
+        storage::set_round_stats(env, round_num, &stats);
+
+        let mut liabilities = storage::get_liabilities(env);
+        liabilities.open_pool = liabilities.open_pool.checked_sub(amount).unwrap();
+        storage::set_liabilities(env, &liabilities);
+
+        // INTERACTIONS
+        let token_client = Self::token_client(env, config.underlying_token.clone());
+        token_client.transfer(&env.current_contract_address(), keeper, &amount);
+
+        events::emit_keeper_paid(env, round_num, keeper, task, amount, stats.prize_pool);
+        Ok(amount)
+    }
+
+    /// Price tiers need ascending min_tickets, a positive price, and fewer bonus than paid tickets
+    fn valid_price_tiers(config: &Config) -> bool {
+        if config.price_tiers.len() > storage::MAX_PRICE_TIERS {
+            return false;
+        }
+        let mut previous_min = 0u32;
+        for tier in config.price_tiers.iter() {
+            if tier.min_tickets <= previous_min || tier.ticket_price <= 0 || tier.bonus_tickets >= tier.min_tickets {
+                return false;
+            }
+            previous_min = tier.min_tickets;
+        }
+        true
+    }
+
+    /// Curves rise by a positive increment, at least one ticket per step, and replace price tiers
+    fn valid_price_curve(config: &Config) -> bool {
+        match config.price_curve {
+            PriceCurve::Flat => true,
+            PriceCurve::Linear(increment) => increment > 0 && config.price_tiers.is_empty(),
+            PriceCurve::Stepped(step_tickets, increment) => {
+                step_tickets > 0 && increment > 0 && config.price_tiers.is_empty()
+            }
+        }
+    }
+
+    /// Bounties are non-negative and, together, stay below the smallest pool a drawable round
+    /// can hold so the winner or refunded players always keep part of it
+    fn valid_bounties(config: &Config) -> bool {
+        let mut total_bps = 0u32;
+        let mut total_fixed = 0i128;
+        for bounty in [&config.draw_bounty, &config.fulfill_bounty] {
+            match bounty {
+                KeeperBounty::None => {}
+                KeeperBounty::Fixed(amount) => {
+                    if *amount < 0 {
+                        return false;
+                    }
+                    total_fixed = total_fixed.saturating_add(*amount);
+                }
+                KeeperBounty::BasisPoints(bps) => total_bps = total_bps.saturating_add(*bps),
+            }
+        }
+        if total_bps >= storage::BASIS_POINTS {
+            return false;
+        }
+        let min_pool = pricing::min_drawable_pool(config);
+        let bps_share = min_pool.checked_mul(total_bps as i128).unwrap() / storage::BASIS_POINTS as i128;
+        total_fixed.saturating_add(bps_share) < min_pool
+    }
+
+    /// Seal the round's participants tree into the round once sales close
+    fn freeze_participants_root(env: &Env, round_num: u32) {
+        let root = merkle::root(env, &storage::get_participants_tree(env, round_num));
+        storage::set_round_participants_root(env, round_num, &root);
+    }
+
+    /// Find the owner of a ticket by binary search over the round's ticket entries
     fn find_winner_by_ticket(
         env: &Env,
         round: u32,
         winning_ticket: u32,
-        _stats: &RoundStats,
+        stats: &RoundStats,
     ) -> Result<Address, Error> {
-        let participants = storage::get_all_participants(env, round);
-        let mut ticket_counter: u32 = 0;
-
-        // Iterate through participants and count their tickets
-        for participant in participants.iter() {
-            let user_tickets = storage::get_user_tickets(env, round, &participant);
-            let ticket_range_end = ticket_counter + user_tickets;
+        if winning_ticket >= stats.total_tickets {
+            return Err(Error::WinnerNotFound);
+        }
 
-            // Check if winning ticket falls in this user's range
-            if winning_ticket >= ticket_counter && winning_ticket < ticket_range_end {
-                return Ok(participant);
+        // Entries cover consecutive ranges in entry order, find the last one starting at or before the ticket
+        let (mut low, mut high) = (0u32, storage::get_participants_tree(env, round).leaf_count);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            let entry = storage::get_ticket_entry(env, round, mid).ok_or(Error::WinnerNotFound)?;
+            if entry.first_ticket <= winning_ticket {
+                low = mid;
+            } else {
+                high = mid;
             }
-
-            ticket_counter = ticket_range_end;
         }
 
-        // This should never happen if stats are correct
-        Err(Error::WinnerNotFound)
+        let entry = storage::get_ticket_entry(env, round, low).ok_or(Error::WinnerNotFound)?;
+        if winning_ticket < entry.first_ticket || winning_ticket >= entry.first_ticket + entry.tickets {
+            return Err(Error::WinnerNotFound);
+        }
+        Ok(entry.participant)
     }
 }
 
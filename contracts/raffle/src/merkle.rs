@@ -0,0 +1,99 @@
+//! Incremental Merkle tree over a round's ticket entries, one leaf per purchase in entry order.
+//!
+//! - leaf: sha256(0x00 || participant xdr || first ticket (u32 BE) || tickets (u32 BE))
+//! - node: sha256(0x01 || left || right)
+//! - empty subtrees hash to the zero hash of their height, the empty leaf being 32 zero bytes
+//!
+//! An inclusion proof is the leaf index plus the `TREE_DEPTH` sibling hashes from the leaf up,
+//! and verifies against the root frozen into the round when sales closed.
+
+use soroban_sdk::{xdr::ToXdr, Bytes, BytesN, Env, Vec};
+
+use crate::types::{MerkleFrontier, TicketEntry};
+
+/// Height of every round's tree, enough for u32 entry indexes
+pub const TREE_DEPTH: u32 = 32;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash of a ticket entry as stored in the tree
+pub fn leaf_hash(env: &Env, entry: &TicketEntry) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &[LEAF_PREFIX]);
+    preimage.append(&entry.participant.clone().to_xdr(env));
+    preimage.extend_from_array(&entry.first_ticket.to_be_bytes());
+    preimage.extend_from_array(&entry.tickets.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Hash of an inner node from its children
+pub fn node_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &[NODE_PREFIX]);
+    preimage.extend_from_array(&left.to_array());
+    preimage.extend_from_array(&right.to_array());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Empty tree with no entries
+pub fn empty(env: &Env) -> MerkleFrontier {
+    MerkleFrontier {
+        leaf_count: 0,
+        branch: Vec::new(env),
+    }
+}
+
+/// Append a leaf, touching only the frontier: the left siblings still waiting for a right one
+pub fn append(env: &Env, tree: &mut MerkleFrontier, leaf: &BytesN<32>) {
+    let mut node = leaf.clone();
+    let mut size = tree.leaf_count + 1;
+
+    for height in 0..TREE_DEPTH {
+        if size & 1 == 1 {
+            if height < tree.branch.len() {
+                tree.branch.set(height, node);
+            } else {
+                tree.branch.push_back(node);
+            }
+            break;
+        }
+        node = node_hash(env, &tree.branch.get(height).unwrap(), &node);
+        size >>= 1;
+    }
+
+    tree.leaf_count += 1;
+}
+
+/// Root over every appended leaf, padding the rest of the tree with empty subtrees
+pub fn root(env: &Env, tree: &MerkleFrontier) -> BytesN<32> {
+    let mut zero = BytesN::from_array(env, &[0u8; 32]);
+    let mut node = zero.clone();
+    let mut size = tree.leaf_count;
+
+    for height in 0..TREE_DEPTH {
+        node = if size & 1 == 1 {
+            node_hash(env, &tree.branch.get(height).unwrap(), &node)
+        } else {
+            node_hash(env, &node, &zero)
+        };
+        zero = node_hash(env, &zero, &zero);
+        size >>= 1;
+    }
+    node
+}
+
+/// Check that `leaf` sits at `index` under `root`, given its siblings from the bottom up
+pub fn verify_proof(env: &Env, root: &BytesN<32>, leaf: &BytesN<32>, index: u32, proof: &Vec<BytesN<32>>) -> bool {
+    if proof.len() != TREE_DEPTH {
+        return false;
+    }
+
+    let mut node = leaf.clone();
+    for (height, sibling) in proof.iter().enumerate() {
+        node = if (index >> height) & 1 == 1 {
+            node_hash(env, &sibling, &node)
+        } else {
+            node_hash(env, &node, &sibling)
+        };
+    }
+    node == *root
+}
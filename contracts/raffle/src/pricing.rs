@@ -0,0 +1,79 @@
+use crate::types::{Config, PriceCurve, PriceTier};
+
+/// Cost of buying `tickets` in a round that already sold `sold` tickets.
+/// Curved rounds charge every ticket its own price; flat rounds apply the largest price tier reached.
+pub fn ticket_cost(config: &Config, sold: u32, tickets: u32) -> i128 {
+    if config.price_curve != PriceCurve::Flat {
+        return (tickets as i128)
+            .checked_mul(config.ticket_price)
+            .unwrap()
+            .checked_add(curve_premium(&config.price_curve, sold, tickets))
+            .unwrap();
+    }
+
+    // A tier's bonus tickets are free, `bonus_tickets` for every `min_tickets` bought
+    let tier = config
+        .price_tiers
+        .iter()
+        .rev()
+        .find(|tier| tier.min_tickets <= tickets)
+        .unwrap_or(PriceTier {
+            min_tickets: 1,
+            ticket_price: config.ticket_price,
+            bonus_tickets: 0,
+        });
+    let paid_tickets = tickets - (tickets / tier.min_tickets) * tier.bonus_tickets;
+    (paid_tickets as i128).checked_mul(tier.ticket_price).unwrap()
+}
+
+/// Price of the next ticket a round that already sold `sold` tickets would sell
+pub fn marginal_price(config: &Config, sold: u32) -> i128 {
+    config
+        .ticket_price
+        .checked_add(curve_premium(&config.price_curve, sold, 1))
+        .unwrap()
+}
+
+/// Lowest prize pool a round can hold once it sold `target_tickets`, before any bounty.
+/// Curves price tickets by position so every way of reaching the target costs the same; tiers
+/// are bounded by their cheapest price per ticket, bonus tickets included.
+pub fn min_drawable_pool(config: &Config) -> i128 {
+    let target = config.target_tickets as i128;
+    if config.price_curve != PriceCurve::Flat {
+        return ticket_cost(config, 0, config.target_tickets);
+    }
+    config
+        .price_tiers
+        .iter()
+        .map(|tier| {
+            let paid_share = (tier.min_tickets - tier.bonus_tickets) as i128;
+            target.checked_mul(tier.ticket_price).unwrap().checked_mul(paid_share).unwrap() / tier.min_tickets as i128
+        })
+        .fold(target.checked_mul(config.ticket_price).unwrap(), i128::min)
+}
+
+/// What the curve adds on top of ticket_price over tickets `sold..sold + tickets`, summed in closed form
+fn curve_premium(curve: &PriceCurve, sold: u32, tickets: u32) -> i128 {
+    match curve {
+        PriceCurve::Flat => 0,
+        // Ticket k costs k * increment extra: increment * (sold + ... + sold + tickets - 1)
+        PriceCurve::Linear(increment) => {
+            let (sold, tickets) = (sold as i128, tickets as i128);
+            let steps = tickets.checked_mul(2 * sold + tickets - 1).unwrap() / 2;
+            steps.checked_mul(*increment).unwrap()
+        }
+        // Ticket k costs (k / step_tickets) * increment extra
+        PriceCurve::Stepped(step_tickets, increment) => {
+            let end = sold.checked_add(tickets).unwrap();
+            let steps = completed_steps(end, *step_tickets) - completed_steps(sold, *step_tickets);
+            steps.checked_mul(*increment).unwrap()
+        }
+    }
+}
+
+/// Sum of `k / step_tickets` for every ticket k below `tickets`
+fn completed_steps(tickets: u32, step_tickets: u32) -> i128 {
+    let (full, rest) = ((tickets / step_tickets) as i128, (tickets % step_tickets) as i128);
+    // Each full step s contributes s per ticket, the partial step after them `full` per ticket
+    (step_tickets as i128) * full * (full - 1) / 2 + rest * full
+}
@@ -1,16 +1,37 @@
-use soroban_sdk::{Address, Env, TryFromVal, Val, Vec};
+use soroban_sdk::{Address, BytesN, Env, TryFromVal, Val, Vec};
 
-use crate::{error::Error, types::{Config, ParticipantBucket, Round, RoundStats, State, Storage, WinnerRecord}};
+use crate::{error::Error, merkle, types::{Config, DrawRandomness, DrawRecord, LeaderboardEntry, LeaderboardKind, Liabilities, MerkleFrontier, ParticipantBucket, RevealPhase, Round, RoundStats, RoundSummary, State, Storage, TicketEntry, WinnerRecord}};
 
 const DAY_IN_LEDGERS: u32 = 17280;
 const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
-const PERSISTENT_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
-const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 20 * DAY_IN_LEDGERS;
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 20 * DAY_IN_LEDGERS;
 
 pub const BUCKET_SIZE: u32 = 100;
 
+/// Maximum number of items returned by paginated views
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Season number under which all-time leaderboards are stored
+pub const ALL_TIME_SEASON: u32 = 0;
+
+/// Reveal window given to commit-reveal rounds unless configured otherwise (~1 hour)
+pub const DEFAULT_REVEAL_WINDOW: u32 = DAY_IN_LEDGERS / 24;
+
+/// Ledgers to wait for the VRF before a draw can be retried, unless configured otherwise (~1 hour)
+pub const DEFAULT_DRAW_TIMEOUT: u32 = DAY_IN_LEDGERS / 24;
+
+/// VRF requests made for a round before it can be refunded, unless configured otherwise
+pub const DEFAULT_MAX_DRAW_ATTEMPTS: u32 = 3;
+
+/// Denominator of basis-point bounties
+pub const BASIS_POINTS: u32 = 10_000;
+
+/// Most price tiers a config can hold
+pub const MAX_PRICE_TIERS: u32 = 10;
+
 pub fn extend_instance_ttl(env: &Env) {
     env.storage()
         .instance()
@@ -18,6 +39,7 @@ pub fn extend_instance_ttl(env: &Env) {
 }
 
 /// Fetch an entry in persistent storage that has a default value if it doesn't exist
+#[allow(clippy::needless_return)]
 fn get_persistent_extend_or_error<V: TryFromVal<Env, Val>>(
     env: &Env,
     key: &Storage,
@@ -35,6 +57,19 @@ fn get_persistent_extend_or_error<V: TryFromVal<Env, Val>>(
     }
 }
 
+/// Fetch an entry in persistent storage, extending its TTL if it exists
+fn get_persistent_extend<V: TryFromVal<Env, Val>>(env: &Env, key: &Storage) -> Option<V> {
+    let result = env.storage().persistent().get(key);
+    if result.is_some() {
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    result
+}
+
 pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&Storage::Admin, admin);
 }
@@ -43,6 +78,18 @@ pub fn get_admin(env: &Env) -> Result<Address, Error> {
     env.storage().instance().get(&Storage::Admin).ok_or(Error::AdminNotFound)?
 }
 
+pub fn set_treasurer(env: &Env, treasurer: &Address) {
+    env.storage().instance().set(&Storage::Treasurer, treasurer);
+}
+
+/// The treasurer defaults to the admin until one is set
+pub fn get_treasurer(env: &Env) -> Result<Address, Error> {
+    match env.storage().instance().get(&Storage::Treasurer) {
+        Some(treasurer) => Ok(treasurer),
+        None => get_admin(env),
+    }
+}
+
 // Config
 pub fn set_config(e: &Env, config: Config) {
     e.storage().instance().set(&Storage::Config, &config);
@@ -52,6 +99,15 @@ pub fn get_config(env: &Env) -> Result<Config, Error> {
     env.storage().instance().get(&Storage::Config).ok_or(Error::ConfigNotFound)?
 }
 
+// Liabilities
+pub fn get_liabilities(env: &Env) -> Liabilities {
+    env.storage().instance().get(&Storage::Liabilities).unwrap_or_default()
+}
+
+pub fn set_liabilities(env: &Env, liabilities: &Liabilities) {
+    env.storage().instance().set(&Storage::Liabilities, liabilities);
+}
+
 // Round
 pub fn create_new_round(env: &Env) {
     let total_rounds = get_total_rounds(env);
@@ -61,6 +117,11 @@ pub fn create_new_round(env: &Env) {
         round: new_round_num,
         state: State::OPEN,
         vrf_request_id: None,
+        draw_seed: None,
+        draw_ledger: 0,
+        draw_attempts: 0,
+        participants_root: None,
+        closed_ledger: 0,
     };
 
     let key = Storage::Round(new_round_num);
@@ -74,6 +135,7 @@ pub fn create_new_round(env: &Env) {
         total_tickets: 0,
         total_participants: 0,
         prize_pool: 0,
+        keeper_bounties: 0,
     };
     let stats_key = Storage::RoundStats(new_round_num);
     env.storage().persistent().set(&stats_key, &stats);
@@ -94,11 +156,13 @@ pub fn set_total_rounds(env: &Env, n: u32) {
     env.storage().instance().set(&Storage::TotalRounds, &n);
 }
 
+#[allow(clippy::needless_borrow)]
 pub fn get_round_by_index(env: &Env, n: u32) -> Result<Round, Error> {
     let key = Storage::Round(n);
     get_persistent_extend_or_error(&env, &key, Error::RoundNotFound)
 }
 
+#[allow(clippy::needless_borrow)]
 pub fn get_current_round(env: &Env) -> Result<Round, Error> {
     let current_round_num = get_current_round_number(env);
     let key = Storage::Round(current_round_num);
@@ -125,9 +189,43 @@ pub fn set_round_state(env: &Env, round: u32, state: State) {
         .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 }
 
-pub fn set_round_vrf_request(env: &Env, round: u32, request_id: u64) {
+pub fn set_round_closed(env: &Env, round: u32) {
+    let mut round_data = get_round_by_index(env, round).unwrap();
+    round_data.state = State::CLOSED;
+    round_data.closed_ledger = env.ledger().sequence();
+    let key = Storage::Round(round);
+    env.storage().persistent().set(&key, &round_data);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn set_round_vrf_request(env: &Env, round: u32, request_id: u64, seed: &BytesN<32>) {
     let mut round_data = get_round_by_index(env, round).unwrap();
     round_data.vrf_request_id = Some(request_id);
+    round_data.draw_seed = Some(seed.clone());
+    round_data.draw_ledger = env.ledger().sequence();
+    round_data.draw_attempts = round_data.draw_attempts.checked_add(1).unwrap();
+    let key = Storage::Round(round);
+    env.storage().persistent().set(&key, &round_data);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn set_round_participants_root(env: &Env, round: u32, root: &BytesN<32>) {
+    let mut round_data = get_round_by_index(env, round).unwrap();
+    round_data.participants_root = Some(root.clone());
+    let key = Storage::Round(round);
+    env.storage().persistent().set(&key, &round_data);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn set_round_draw_seed(env: &Env, round: u32, seed: &BytesN<32>) {
+    let mut round_data = get_round_by_index(env, round).unwrap();
+    round_data.draw_seed = Some(seed.clone());
     let key = Storage::Round(round);
     env.storage().persistent().set(&key, &round_data);
     env.storage()
@@ -152,7 +250,7 @@ pub fn set_round_stats(env: &Env, round: u32, stats: &RoundStats) {
 // User Tickets
 pub fn get_user_tickets(env: &Env, round: u32, user: &Address) -> u32 {
     let key = Storage::UserTickets(round, user.clone());
-    env.storage().persistent().get(&key).unwrap_or(0)
+    get_persistent_extend(env, &key).unwrap_or(0)
 }
 
 pub fn add_user_tickets(env: &Env, round: u32, user: &Address, num_tickets: u32) {
@@ -165,15 +263,37 @@ pub fn add_user_tickets(env: &Env, round: u32, user: &Address, num_tickets: u32)
         .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 }
 
+pub fn remove_user_tickets(env: &Env, round: u32, user: &Address) {
+    let key = Storage::UserTickets(round, user.clone());
+    env.storage().persistent().remove(&key);
+}
+
+// User Paid
+pub fn get_user_paid(env: &Env, round: u32, user: &Address) -> i128 {
+    let key = Storage::UserPaid(round, user.clone());
+    get_persistent_extend(env, &key).unwrap_or(0)
+}
+
+pub fn add_user_paid(env: &Env, round: u32, user: &Address, amount: i128) {
+    let new_total = get_user_paid(env, round, user).checked_add(amount).unwrap();
+    let key = Storage::UserPaid(round, user.clone());
+    env.storage().persistent().set(&key, &new_total);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn remove_user_paid(env: &Env, round: u32, user: &Address) {
+    let key = Storage::UserPaid(round, user.clone());
+    env.storage().persistent().remove(&key);
+}
+
 // Participant Buckets
 pub fn get_participant_bucket(env: &Env, round: u32, bucket_idx: u32) -> ParticipantBucket {
     let key = Storage::ParticipantBucket(round, bucket_idx);
-    env.storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(ParticipantBucket {
-            participants: Vec::new(env),
-        })
+    get_persistent_extend(env, &key).unwrap_or(ParticipantBucket {
+        participants: Vec::new(env),
+    })
 }
 
 pub fn set_participant_bucket(env: &Env, round: u32, bucket_idx: u32, bucket: &ParticipantBucket) {
@@ -184,6 +304,11 @@ pub fn set_participant_bucket(env: &Env, round: u32, bucket_idx: u32, bucket: &P
         .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 }
 
+pub fn remove_participant_bucket(env: &Env, round: u32, bucket_idx: u32) {
+    let key = Storage::ParticipantBucket(round, bucket_idx);
+    env.storage().persistent().remove(&key);
+}
+
 pub fn add_participant(env: &Env, round: u32, participant: &Address) {
     let mut stats = get_round_stats(env, round).unwrap();
     let bucket_idx = stats.total_participants / BUCKET_SIZE;
@@ -196,6 +321,7 @@ pub fn add_participant(env: &Env, round: u32, participant: &Address) {
     set_round_stats(env, round, &stats);
 }
 
+#[allow(clippy::manual_div_ceil)]
 pub fn get_all_participants(env: &Env, round: u32) -> Vec<Address> {
     let stats = get_round_stats(env, round).unwrap();
     let total_buckets = (stats.total_participants + BUCKET_SIZE - 1) / BUCKET_SIZE;
@@ -210,6 +336,63 @@ pub fn get_all_participants(env: &Env, round: u32) -> Vec<Address> {
     all_participants
 }
 
+/// Extend the TTL of a round's own entries, then of up to `max_entries` participants and
+/// ticket entries from `start`, participants first. Returns how many are left past the chunk.
+pub fn bump_round(env: &Env, round: u32, start: u32, max_entries: u32) -> Result<u32, Error> {
+    get_round_by_index(env, round)?;
+    let stats = get_round_stats(env, round)?;
+    get_winner_record(env, round);
+    get_round_summary(env, round);
+    get_reveal_phase(env, round);
+    get_draw_randomness(env, round);
+    get_draw_record(env, round);
+    get_refunded_paid(env, round);
+    let entry_count = get_participants_tree(env, round).leaf_count;
+
+    let total = stats.total_participants + entry_count;
+    let end = start.saturating_add(max_entries).min(total);
+    let mut index = start;
+
+    // Reading buckets and per-participant entries extends them
+    let mut bucket: Option<(u32, ParticipantBucket)> = None;
+    while index < end.min(stats.total_participants) {
+        let bucket_idx = index / BUCKET_SIZE;
+        if bucket.as_ref().is_none_or(|(loaded, _)| *loaded != bucket_idx) {
+            bucket = Some((bucket_idx, get_participant_bucket(env, round, bucket_idx)));
+        }
+        if let Some((_, current)) = &bucket {
+            if let Some(participant) = current.participants.get(index % BUCKET_SIZE) {
+                get_user_tickets(env, round, &participant);
+                get_user_paid(env, round, &participant);
+                get_commitment(env, round, &participant);
+                get_revealed(env, round, &participant);
+                is_refunded(env, round, &participant);
+            }
+        }
+        index += 1;
+    }
+
+    while index < end {
+        get_ticket_entry(env, round, index - stats.total_participants);
+        index += 1;
+    }
+    Ok(total - end)
+}
+
+// Round Summaries
+pub fn get_round_summary(env: &Env, round: u32) -> Option<RoundSummary> {
+    let key = Storage::RoundSummary(round);
+    get_persistent_extend(env, &key)
+}
+
+pub fn set_round_summary(env: &Env, round: u32, summary: &RoundSummary) {
+    let key = Storage::RoundSummary(round);
+    env.storage().persistent().set(&key, summary);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
 // Winner Records
 pub fn set_winner_record(env: &Env, round: u32, record: &WinnerRecord) {
     let key = Storage::WinnerRecord(round);
@@ -217,30 +400,42 @@ pub fn set_winner_record(env: &Env, round: u32, record: &WinnerRecord) {
     env.storage()
         .persistent()
         .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
 
-    // Track user's winning rounds
-    let winner = &record.winner;
+/// Index a newly drawn winner: once in their win history and once in their unclaimed set
+pub fn add_winning_round(env: &Env, winner: &Address, round: u32) {
     let mut winning_rounds = get_user_winning_rounds(env, winner);
     winning_rounds.push_back(round);
     set_user_winning_rounds(env, winner, &winning_rounds);
+
+    let mut unclaimed_rounds = get_user_unclaimed_rounds(env, winner);
+    unclaimed_rounds.push_back(round);
+    set_user_unclaimed_rounds(env, winner, &unclaimed_rounds);
 }
 
 pub fn get_winner_record(env: &Env, round: u32) -> Option<WinnerRecord> {
     let key = Storage::WinnerRecord(round);
-    env.storage().persistent().get(&key)
+    get_persistent_extend(env, &key)
 }
 
 pub fn update_winner_claimed(env: &Env, round: u32) -> Result<(), Error> {
     let mut record = get_winner_record(env, round).ok_or(Error::WinnerNotFound)?;
     record.claimed = true;
     set_winner_record(env, round, &record);
+
+    // Claimed rounds leave the unclaimed set so it only ever holds pending prizes
+    let mut unclaimed_rounds = get_user_unclaimed_rounds(env, &record.winner);
+    if let Some(idx) = unclaimed_rounds.first_index_of(round) {
+        unclaimed_rounds.remove(idx);
+        set_user_unclaimed_rounds(env, &record.winner, &unclaimed_rounds);
+    }
     Ok(())
 }
 
 // User Winning Rounds
 pub fn get_user_winning_rounds(env: &Env, user: &Address) -> Vec<u32> {
     let key = Storage::UserWinningRounds(user.clone());
-    env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+    get_persistent_extend(env, &key).unwrap_or(Vec::new(env))
 }
 
 pub fn set_user_winning_rounds(env: &Env, user: &Address, rounds: &Vec<u32>) {
@@ -250,3 +445,195 @@ pub fn set_user_winning_rounds(env: &Env, user: &Address, rounds: &Vec<u32>) {
         .persistent()
         .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 }
+
+// User Unclaimed Rounds
+pub fn get_user_unclaimed_rounds(env: &Env, user: &Address) -> Vec<u32> {
+    let key = Storage::UserUnclaimedRounds(user.clone());
+    get_persistent_extend(env, &key).unwrap_or(Vec::new(env))
+}
+
+pub fn set_user_unclaimed_rounds(env: &Env, user: &Address, rounds: &Vec<u32>) {
+    let key = Storage::UserUnclaimedRounds(user.clone());
+    if rounds.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, rounds);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Leaderboard Seasons
+pub fn get_current_season(env: &Env) -> u32 {
+    env.storage().instance().get(&Storage::CurrentSeason).unwrap_or(1)
+}
+
+pub fn set_current_season(env: &Env, season: u32) {
+    env.storage().instance().set(&Storage::CurrentSeason, &season);
+}
+
+// Leaderboards
+pub fn get_leaderboard(env: &Env, kind: LeaderboardKind, season: u32) -> Vec<LeaderboardEntry> {
+    let key = Storage::Leaderboard(kind, season);
+    get_persistent_extend(env, &key).unwrap_or(Vec::new(env))
+}
+
+pub fn set_leaderboard(env: &Env, kind: LeaderboardKind, season: u32, board: &Vec<LeaderboardEntry>) {
+    let key = Storage::Leaderboard(kind, season);
+    env.storage().persistent().set(&key, board);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_player_total(env: &Env, kind: LeaderboardKind, season: u32, player: &Address) -> i128 {
+    let key = Storage::PlayerTotal(kind, season, player.clone());
+    get_persistent_extend(env, &key).unwrap_or(0)
+}
+
+pub fn set_player_total(env: &Env, kind: LeaderboardKind, season: u32, player: &Address, total: i128) {
+    let key = Storage::PlayerTotal(kind, season, player.clone());
+    env.storage().persistent().set(&key, &total);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Commit-Reveal
+pub fn get_commitment(env: &Env, round: u32, user: &Address) -> Option<BytesN<32>> {
+    let key = Storage::Commitment(round, user.clone());
+    get_persistent_extend(env, &key)
+}
+
+pub fn set_commitment(env: &Env, round: u32, user: &Address, commitment: &BytesN<32>) {
+    let key = Storage::Commitment(round, user.clone());
+    env.storage().persistent().set(&key, commitment);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_revealed(env: &Env, round: u32, user: &Address) -> Option<BytesN<32>> {
+    let key = Storage::Revealed(round, user.clone());
+    get_persistent_extend(env, &key)
+}
+
+pub fn set_revealed(env: &Env, round: u32, user: &Address, secret: &BytesN<32>) {
+    let key = Storage::Revealed(round, user.clone());
+    env.storage().persistent().set(&key, secret);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+/// Drop a participant's commitment and reveal once the round is pruned
+pub fn remove_commit_reveal(env: &Env, round: u32, user: &Address) {
+    env.storage().persistent().remove(&Storage::Commitment(round, user.clone()));
+    env.storage().persistent().remove(&Storage::Revealed(round, user.clone()));
+}
+
+pub fn get_reveal_phase(env: &Env, round: u32) -> RevealPhase {
+    let key = Storage::RevealPhase(round);
+    get_persistent_extend(env, &key).unwrap_or(RevealPhase {
+        commitments: 0,
+        reveals: 0,
+        deadline_ledger: 0,
+        accumulator: BytesN::from_array(env, &[0u8; 32]),
+    })
+}
+
+pub fn set_reveal_phase(env: &Env, round: u32, phase: &RevealPhase) {
+    let key = Storage::RevealPhase(round);
+    env.storage().persistent().set(&key, phase);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Refunds
+pub fn is_refunded(env: &Env, round: u32, user: &Address) -> bool {
+    let key = Storage::Refunded(round, user.clone());
+    get_persistent_extend(env, &key).unwrap_or(false)
+}
+
+pub fn set_refunded(env: &Env, round: u32, user: &Address) {
+    let key = Storage::Refunded(round, user.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_refunded_paid(env: &Env, round: u32) -> i128 {
+    let key = Storage::RefundedPaid(round);
+    get_persistent_extend(env, &key).unwrap_or(0)
+}
+
+pub fn set_refunded_paid(env: &Env, round: u32, paid: i128) {
+    let key = Storage::RefundedPaid(round);
+    env.storage().persistent().set(&key, &paid);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Draw Randomness
+pub fn get_draw_randomness(env: &Env, round: u32) -> Option<DrawRandomness> {
+    let key = Storage::DrawRandomness(round);
+    get_persistent_extend(env, &key)
+}
+
+pub fn set_draw_randomness(env: &Env, round: u32, randomness: &DrawRandomness) {
+    let key = Storage::DrawRandomness(round);
+    env.storage().persistent().set(&key, randomness);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Draw Records
+pub fn get_draw_record(env: &Env, round: u32) -> Option<DrawRecord> {
+    let key = Storage::DrawRecord(round);
+    get_persistent_extend(env, &key)
+}
+
+pub fn set_draw_record(env: &Env, round: u32, record: &DrawRecord) {
+    let key = Storage::DrawRecord(round);
+    env.storage().persistent().set(&key, record);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+// Ticket Entries
+pub fn get_ticket_entry(env: &Env, round: u32, index: u32) -> Option<TicketEntry> {
+    let key = Storage::TicketEntry(round, index);
+    get_persistent_extend(env, &key)
+}
+
+pub fn set_ticket_entry(env: &Env, round: u32, index: u32, entry: &TicketEntry) {
+    let key = Storage::TicketEntry(round, index);
+    env.storage().persistent().set(&key, entry);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn remove_ticket_entry(env: &Env, round: u32, index: u32) {
+    env.storage().persistent().remove(&Storage::TicketEntry(round, index));
+}
+
+// Participants Tree
+pub fn get_participants_tree(env: &Env, round: u32) -> MerkleFrontier {
+    let key = Storage::ParticipantsTree(round);
+    get_persistent_extend(env, &key).unwrap_or_else(|| merkle::empty(env))
+}
+
+pub fn set_participants_tree(env: &Env, round: u32, tree: &MerkleFrontier) {
+    let key = Storage::ParticipantsTree(round);
+    env.storage().persistent().set(&key, tree);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
@@ -2,21 +2,22 @@
 extern crate std;
 
 use super::*;
+use types::{PriceCurve, PriceTier};
 use soroban_sdk::{
-    testutils::{Address as _},
+    testutils::{Address as _, Events as _},
     token::{Client as TokenClient, StellarAssetClient as StellarAssetAdmin},
-    Address, Env,
+    Address, Env, Symbol, TryFromVal,
 };
 
 mod mock_vrf {
-    use soroban_sdk::{contract, contractimpl, Address, Env};
+    use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
 
     #[contract]
     pub struct MockVRF;
 
     #[contractimpl]
     impl MockVRF {
-        pub fn request_random(_env: Env, _requester: Address) -> u64 {
+        pub fn request_random(_env: Env, _requester: Address, _seed: BytesN<32>) -> u64 {
             // Return a predictable value for unit testing
             42
         }
@@ -33,6 +34,21 @@ fn create_token<'a>(env: &Env) -> (Address, TokenClient<'a>, StellarAssetAdmin<'
     (token_id.address(), token_client, admin_client)
 }
 
+// Count raffle events with the given name published by the last invocation
+fn count_raffle_events(env: &Env, raffle_id: &Address, name: &str) -> u32 {
+    let prefix = Symbol::new(env, events::EVENT_TOPIC_PREFIX);
+    let name = Symbol::new(env, name);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            contract == raffle_id
+                && Symbol::try_from_val(env, &topics.get(0).unwrap()).ok() == Some(prefix.clone())
+                && Symbol::try_from_val(env, &topics.get(1).unwrap()).ok() == Some(name.clone())
+        })
+        .count() as u32
+}
+
 // Helper function to initialize raffle contract for testing
 fn setup_raffle<'a>(env: &Env) -> (Address, LuckyLedgersRaffleClient<'a>, Address, TokenClient<'a>, StellarAssetAdmin<'a>) {
     let admin = Address::generate(env);
@@ -174,6 +190,7 @@ fn test_enter_twice_same_user() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_is_ready_to_draw() {
     let env = Env::default();
     let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
@@ -299,3 +316,614 @@ fn test_ticket_cap_enforcement() {
     assert_eq!(stats.total_tickets, 10); // Unchanged
     assert_eq!(stats.prize_pool, 10_000_000i128); // Unchanged
 }
+
+#[test]
+fn test_enter_exact_fails_instead_of_truncating() {
+    let env = Env::default();
+    let (_, raffle_client, _, token_client, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &200_000_000i128);
+
+    assert_eq!(raffle_client.enter_exact(&alice, &8, &8_000_000i128), 8);
+
+    // Only 2 tickets are left under the wallet cap, so 5 can't be bought exactly
+    assert_eq!(raffle_client.try_enter_exact(&alice, &5, &5_000_000i128), Err(Ok(Error::InsufficientTickets)));
+    assert_eq!(raffle_client.try_enter_exact(&alice, &2, &1_999_999i128), Err(Ok(Error::MaxCostExceeded)));
+    assert_eq!(raffle_client.try_enter_exact(&alice, &0, &0), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(token_client.balance(&alice), 192_000_000i128);
+
+    assert_eq!(raffle_client.enter_exact(&alice, &2, &2_000_000i128), 10);
+
+    // At the cap a strict purchase is an error, not a silent no-op
+    assert_eq!(raffle_client.try_enter_exact(&alice, &1, &1_000_000i128), Err(Ok(Error::InsufficientTickets)));
+    assert_eq!(raffle_client.get_round_stats(&1).total_tickets, 10);
+}
+
+#[test]
+fn test_quote_entry() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &200_000_000i128);
+    token_admin.mint(&bob, &200_000_000i128);
+
+    let quote = raffle_client.quote_entry(&alice, &4);
+    assert_eq!(quote.tickets, 4);
+    assert_eq!(quote.cost, 4_000_000i128);
+    assert_eq!(quote.odds_bps, 10_000);
+
+    raffle_client.enter(&alice, &8);
+    raffle_client.enter(&bob, &2);
+
+    // Alice can only add 2 more, ending with 10 of 12 tickets
+    let quote = raffle_client.quote_entry(&alice, &5);
+    assert_eq!(quote.tickets, 2);
+    assert_eq!(quote.cost, 2_000_000i128);
+    assert_eq!(quote.user_tickets, 10);
+    assert_eq!(quote.total_tickets, 12);
+    assert_eq!(quote.odds_bps, 8_333);
+
+    // The quote is what enter then buys
+    assert_eq!(raffle_client.enter(&alice, &5), quote.user_tickets);
+    assert_eq!(raffle_client.get_round_stats(&1).total_tickets, quote.total_tickets);
+
+    let quote = raffle_client.quote_entry(&alice, &1);
+    assert_eq!(quote.tickets, 0);
+    assert_eq!(quote.cost, 0);
+}
+
+#[test]
+fn test_price_tiers_discount_purchases() {
+    use soroban_sdk::{xdr::ScVal, Event as _, Val};
+
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    // 5 tickets for the price of 4, and 10 or more at 0.8 each
+    let mut config = raffle_client.get_config();
+    config.price_tiers = Vec::from_array(
+        &env,
+        [
+            PriceTier { min_tickets: 5, ticket_price: 1_000_000, bonus_tickets: 1 },
+            PriceTier { min_tickets: 10, ticket_price: 800_000, bonus_tickets: 0 },
+        ],
+    );
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    assert_eq!(raffle_client.quote_entry(&alice, &4).cost, 4_000_000i128);
+    assert_eq!(raffle_client.quote_entry(&alice, &5).cost, 4_000_000i128);
+    assert_eq!(raffle_client.quote_entry(&alice, &9).cost, 8_000_000i128);
+    assert_eq!(raffle_client.quote_entry(&alice, &10).cost, 8_000_000i128);
+
+    raffle_client.enter(&alice, &5);
+    let expected = events::PlayerEntered {
+        round: 1,
+        player: alice.clone(),
+        num_tickets: 5,
+        amount_paid: 4_000_000,
+        total_tickets: 5,
+        entry_index: 0,
+        marginal_price: 1_000_000,
+    };
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    let to_scval = |val: &Val| ScVal::try_from_val(&env, val).unwrap();
+    assert_eq!(contract, raffle_id);
+    assert_eq!(topics, expected.topics(&env));
+    assert_eq!(to_scval(&data), to_scval(&expected.data(&env)));
+
+    assert_eq!(token_client.balance(&alice), 96_000_000i128);
+    assert_eq!(raffle_client.get_user_tickets(&1, &alice), 5);
+    assert_eq!(raffle_client.get_round_stats(&1).prize_pool, 4_000_000i128);
+}
+
+#[test]
+fn test_price_tier_validation() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let tier = |min_tickets: u32, ticket_price: i128, bonus_tickets: u32| PriceTier {
+        min_tickets,
+        ticket_price,
+        bonus_tickets,
+    };
+    let invalid = [
+        Vec::from_array(&env, [tier(0, 1_000_000, 0)]),
+        Vec::from_array(&env, [tier(5, 0, 0)]),
+        Vec::from_array(&env, [tier(5, 1_000_000, 5)]),
+        Vec::from_array(&env, [tier(5, 1_000_000, 1), tier(5, 900_000, 0)]),
+        Vec::from_array(&env, [tier(10, 1_000_000, 1), tier(5, 900_000, 0)]),
+    ];
+    for price_tiers in invalid {
+        let mut config = raffle_client.get_config();
+        config.price_tiers = price_tiers;
+        assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    }
+
+    let mut config = raffle_client.get_config();
+    config.price_tiers = Vec::new(&env);
+    for min_tickets in 1..=storage::MAX_PRICE_TIERS + 1 {
+        config.price_tiers.push_back(tier(min_tickets, 1_000_000, 0));
+    }
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    config.price_tiers.pop_back();
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_linear_price_curve() {
+    use soroban_sdk::{xdr::ScVal, Event as _, Val};
+
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, token_client, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    // Ticket k of the round costs 1 + 0.01 * k
+    let mut config = raffle_client.get_config();
+    config.price_curve = PriceCurve::Linear(10_000);
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    let quote = raffle_client.quote_entry(&alice, &5);
+    assert_eq!(quote.cost, 5_100_000i128);
+    assert_eq!(quote.marginal_price, 1_000_000i128);
+
+    let quote = raffle_client.quote_entry(&alice, &3);
+    assert_eq!(quote.cost, 3_030_000i128);
+    raffle_client.enter(&alice, &3);
+    assert_eq!(token_client.balance(&alice), 100_000_000i128 - quote.cost);
+
+    // Buying in two parts costs the same as buying at once
+    let quote = raffle_client.quote_entry(&bob, &2);
+    assert_eq!(quote.cost, 2_070_000i128);
+    assert_eq!(quote.marginal_price, 1_030_000i128);
+    raffle_client.enter(&bob, &2);
+
+    // The entry reports the price the next buyer pays
+    let (contract, _, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, raffle_id);
+    let entered = events::PlayerEntered {
+        round: 1,
+        player: bob.clone(),
+        num_tickets: 2,
+        amount_paid: 2_070_000,
+        total_tickets: 5,
+        entry_index: 1,
+        marginal_price: 1_050_000,
+    };
+    let to_scval = |val: &Val| ScVal::try_from_val(&env, val).unwrap();
+    assert_eq!(to_scval(&data), to_scval(&entered.data(&env)));
+    assert_eq!(raffle_client.get_round_stats(&1).prize_pool, 5_100_000i128);
+}
+
+#[test]
+fn test_stepped_price_curve() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    // Every 4 tickets sold raise the price by 0.1
+    let mut config = raffle_client.get_config();
+    config.price_curve = PriceCurve::Stepped(4, 100_000);
+    raffle_client.update_config(&config);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    assert_eq!(raffle_client.quote_entry(&alice, &4).cost, 4_000_000i128);
+    assert_eq!(raffle_client.quote_entry(&alice, &6).cost, 6_200_000i128);
+    raffle_client.enter(&alice, &6);
+
+    // Tickets 6 and 7 sit in the second step, 8 to 10 in the third
+    let quote = raffle_client.quote_entry(&bob, &5);
+    assert_eq!(quote.marginal_price, 1_100_000i128);
+    assert_eq!(quote.cost, 5_800_000i128);
+    raffle_client.enter(&bob, &5);
+    assert_eq!(raffle_client.quote_entry(&bob, &1).marginal_price, 1_200_000i128);
+}
+
+#[test]
+fn test_price_curve_validation() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    for price_curve in [
+        PriceCurve::Linear(0),
+        PriceCurve::Linear(-1),
+        PriceCurve::Stepped(0, 100_000),
+        PriceCurve::Stepped(4, 0),
+    ] {
+        let mut config = raffle_client.get_config();
+        config.price_curve = price_curve;
+        assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    }
+
+    // A curve replaces price tiers rather than stacking with them
+    let mut config = raffle_client.get_config();
+    config.price_curve = PriceCurve::Linear(10_000);
+    config.price_tiers = Vec::from_array(
+        &env,
+        [PriceTier { min_tickets: 5, ticket_price: 1_000_000, bonus_tickets: 1 }],
+    );
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+    config.price_tiers = Vec::new(&env);
+    raffle_client.update_config(&config);
+}
+
+#[test]
+fn test_buyer_leaderboard_is_sorted() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+    token_admin.mint(&charlie, &100_000_000i128);
+
+    raffle_client.enter(&alice, &2);
+    raffle_client.enter(&bob, &5);
+    raffle_client.enter(&charlie, &3);
+    raffle_client.enter(&alice, &4); // Alice moves to the top with 6
+
+    let season_board = raffle_client.get_leaderboard(&LeaderboardKind::TopBuyers, &Some(1));
+    assert_eq!(season_board.len(), 3);
+    assert_eq!(season_board.get(0).unwrap(), LeaderboardEntry { player: alice.clone(), total: 6 });
+    assert_eq!(season_board.get(1).unwrap(), LeaderboardEntry { player: bob.clone(), total: 5 });
+    assert_eq!(season_board.get(2).unwrap(), LeaderboardEntry { player: charlie.clone(), total: 3 });
+
+    // All-time board mirrors the first season
+    let all_time_board = raffle_client.get_leaderboard(&LeaderboardKind::TopBuyers, &None);
+    assert_eq!(all_time_board, season_board);
+
+    // Nobody has won yet
+    assert_eq!(raffle_client.get_leaderboard(&LeaderboardKind::TopWinners, &None).len(), 0);
+}
+
+#[test]
+fn test_leaderboard_is_bounded() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    // 60 buyers, later buyers buy more tickets and should push earlier ones off
+    let mut buyers = std::vec::Vec::new();
+    for i in 0..60u32 {
+        let user = Address::generate(&env);
+        token_admin.mint(&user, &100_000_000i128);
+        raffle_client.enter(&user, &(1 + i / 6));
+        buyers.push(user);
+    }
+
+    let board = raffle_client.get_leaderboard(&LeaderboardKind::TopBuyers, &None);
+    assert_eq!(board.len(), leaderboard::LEADERBOARD_SIZE);
+
+    // Sorted descending
+    for i in 1..board.len() {
+        assert!(board.get(i - 1).unwrap().total >= board.get(i).unwrap().total);
+    }
+
+    // The top buyer bought the max and the first (1 ticket) buyers were dropped
+    assert_eq!(board.get(0).unwrap().total, 10);
+    assert!(!board.iter().any(|entry| entry.player == buyers[0]));
+}
+
+#[test]
+fn test_new_season_archives_boards() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    token_admin.mint(&bob, &100_000_000i128);
+
+    assert_eq!(raffle_client.get_current_season(), 1);
+    raffle_client.enter(&alice, &3);
+
+    // Admin rolls the season over
+    assert_eq!(raffle_client.start_new_season(), 2);
+    assert_eq!(raffle_client.get_current_season(), 2);
+
+    raffle_client.enter(&bob, &2);
+
+    // Season 1 board is frozen
+    let season1 = raffle_client.get_leaderboard(&LeaderboardKind::TopBuyers, &Some(1));
+    assert_eq!(season1.len(), 1);
+    assert_eq!(season1.get(0).unwrap(), LeaderboardEntry { player: alice.clone(), total: 3 });
+
+    // Season 2 only contains new activity
+    let season2 = raffle_client.get_leaderboard(&LeaderboardKind::TopBuyers, &Some(2));
+    assert_eq!(season2.len(), 1);
+    assert_eq!(season2.get(0).unwrap(), LeaderboardEntry { player: bob.clone(), total: 2 });
+
+    // All-time keeps both
+    let all_time = raffle_client.get_leaderboard(&LeaderboardKind::TopBuyers, &None);
+    assert_eq!(all_time.len(), 2);
+    assert_eq!(all_time.get(0).unwrap().player, alice);
+}
+
+#[test]
+fn test_treasurer_defaults_to_admin() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    assert_eq!(raffle_client.treasurer(), raffle_client.admin());
+
+    let treasurer = Address::generate(&env);
+    raffle_client.set_treasurer(&treasurer);
+    assert_eq!(raffle_client.treasurer(), treasurer);
+}
+
+#[test]
+fn test_recover_foreign_tokens() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, _) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let (foreign_id, foreign_client, foreign_admin) = create_token(&env);
+    foreign_admin.mint(&raffle_id, &5_000i128);
+
+    let to = Address::generate(&env);
+    raffle_client.recover_tokens(&foreign_id, &to, &5_000i128);
+
+    assert_eq!(foreign_client.balance(&to), 5_000i128);
+    assert_eq!(foreign_client.balance(&raffle_id), 0);
+}
+
+#[test]
+fn test_recover_underlying_surplus_only() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, token_id, token_client, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &100_000_000i128);
+    raffle_client.enter(&alice, &5);
+
+    // Stray transfer on top of the open pool
+    token_admin.mint(&raffle_id, &2_000i128);
+
+    let to = Address::generate(&env);
+
+    // Cannot dip into the open pool
+    let result = raffle_client.try_recover_tokens(&token_id, &to, &2_001i128);
+    assert_eq!(result, Err(Ok(Error::InsufficientSurplus)));
+
+    // Zero or negative amounts are rejected
+    let result = raffle_client.try_recover_tokens(&token_id, &to, &0i128);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    // The surplus itself can be recovered
+    raffle_client.recover_tokens(&token_id, &to, &2_000i128);
+    assert_eq!(token_client.balance(&to), 2_000i128);
+    assert_eq!(token_client.balance(&raffle_id), 5_000_000i128);
+    assert_eq!(raffle_client.audit().surplus, 0);
+}
+
+#[test]
+fn test_recover_tokens_requires_treasurer() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, token_id, _, token_admin) = setup_raffle(&env);
+
+    env.mock_all_auths();
+    token_admin.mint(&raffle_id, &1_000i128);
+    raffle_client.set_treasurer(&Address::generate(&env));
+    env.set_auths(&[]);
+
+    let result = raffle_client.try_recover_tokens(&token_id, &Address::generate(&env), &1_000i128);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_prune_round_requires_finalized_round() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &10_000_000i128);
+    raffle_client.enter(&alice, &1);
+
+    // Round 1 is still OPEN
+    let result = raffle_client.try_prune_round(&1, &10);
+    assert_eq!(result, Err(Ok(Error::InvalidState)));
+    assert_eq!(raffle_client.get_user_tickets(&1, &alice), 1);
+    assert!(raffle_client.get_round_summary(&1).is_none());
+}
+
+#[test]
+fn test_events_carry_versioned_prefix() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &10_000_000i128);
+    raffle_client.enter(&alice, &1);
+
+    assert_eq!(count_raffle_events(&env, &raffle_id, "player_entered"), 1);
+    assert_eq!(raffle_client.event_schema_version(), 1);
+}
+
+#[test]
+fn test_ready_to_draw_emitted_once_per_round() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let users: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+    for user in users.iter() {
+        token_admin.mint(user, &100_000_000i128);
+    }
+
+    // 24 tickets: below the target of 25
+    raffle_client.enter(&users[0], &10);
+    raffle_client.enter(&users[1], &10);
+    raffle_client.enter(&users[2], &4);
+    assert_eq!(count_raffle_events(&env, &raffle_id, "ready_to_draw"), 0);
+
+    // The entry crossing the target announces it
+    raffle_client.enter(&users[3], &2);
+    assert_eq!(count_raffle_events(&env, &raffle_id, "ready_to_draw"), 1);
+
+    // Later entries stay quiet
+    raffle_client.enter(&users[3], &1);
+    assert_eq!(count_raffle_events(&env, &raffle_id, "ready_to_draw"), 0);
+    raffle_client.enter(&users[2], &1);
+    assert_eq!(count_raffle_events(&env, &raffle_id, "ready_to_draw"), 0);
+}
+
+#[test]
+fn test_admin_actions_emit_events() {
+    let env = Env::default();
+    let (raffle_id, raffle_client, _, _, _) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    raffle_client.set_new_admin(&Address::generate(&env));
+    assert_eq!(count_raffle_events(&env, &raffle_id, "admin_changed"), 1);
+
+    raffle_client.set_treasurer(&Address::generate(&env));
+    assert_eq!(count_raffle_events(&env, &raffle_id, "treasurer_changed"), 1);
+
+    raffle_client.start_new_season();
+    assert_eq!(count_raffle_events(&env, &raffle_id, "season_started"), 1);
+
+    let mut config = raffle_client.get_config();
+    config.ticket_price = 2_000_000i128;
+    raffle_client.update_config(&config);
+    assert_eq!(count_raffle_events(&env, &raffle_id, "config_updated"), 1);
+    assert_eq!(raffle_client.get_config().ticket_price, 2_000_000i128);
+}
+
+#[test]
+fn test_update_config_rejects_invalid_values() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, _) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    let mut config = raffle_client.get_config();
+    config.ticket_price = 0;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    let mut config = raffle_client.get_config();
+    config.target_tickets = 0;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+}
+
+#[test]
+fn test_update_config_only_between_rounds() {
+    let env = Env::default();
+    let (_, raffle_client, _, _, token_admin) = setup_raffle(&env);
+    env.mock_all_auths();
+
+    // The token the pool is held in is fixed
+    let mut config = raffle_client.get_config();
+    config.underlying_token = Address::generate(&env);
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::InvalidConfig)));
+
+    // Nothing changes under players who already bought in
+    let alice = Address::generate(&env);
+    token_admin.mint(&alice, &10_000_000i128);
+    raffle_client.enter(&alice, &1);
+    let mut config = raffle_client.get_config();
+    config.ticket_price = 2_000_000;
+    assert_eq!(raffle_client.try_update_config(&config), Err(Ok(Error::ConfigLocked)));
+    assert_eq!(raffle_client.get_config().ticket_price, 1_000_000i128);
+}
+
+// Distinct randomness per sample, the draw hashes it before use
+fn sample_randomness(env: &Env, sample: u32) -> BytesN<32> {
+    let mut bytes = [0u8; 32];
+    bytes[..4].copy_from_slice(&sample.to_be_bytes());
+    BytesN::from_array(env, &bytes)
+}
+
+// Pearson's chi-squared statistic of observed counts against a uniform expectation
+fn chi_squared(counts: &[u32], samples: u32) -> f64 {
+    let expected = samples as f64 / counts.len() as f64;
+    counts
+        .iter()
+        .map(|&count| {
+            let diff = count as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+#[test]
+fn test_draw_index_is_uniform() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    // 9 tickets doesn't divide 2^64, so this also exercises the rejection bound
+    let samples = 9_000u32;
+    let mut counts = [0u32; 9];
+    for sample in 0..samples {
+        let randomness = sample_randomness(&env, sample);
+        let index = commitment::draw_index(&env, &randomness, commitment::WINNER_DRAW, 9);
+        counts[index as usize] += 1;
+    }
+
+    // Critical value for 8 degrees of freedom at p = 0.001
+    assert!(chi_squared(&counts, samples) < 26.12, "{:?}", counts);
+}
+
+#[test]
+fn test_draws_from_one_seed_are_independent() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    // Pairs of draws from the same randomness should fill every cell evenly
+    let samples = 8_000u32;
+    let mut counts = [0u32; 16];
+    for sample in 0..samples {
+        let randomness = sample_randomness(&env, sample);
+        let first = commitment::draw_index(&env, &randomness, 0, 4);
+        let second = commitment::draw_index(&env, &randomness, 1, 4);
+        counts[(first * 4 + second) as usize] += 1;
+    }
+
+    // Critical value for 15 degrees of freedom at p = 0.001
+    assert!(chi_squared(&counts, samples) < 37.70, "{:?}", counts);
+}
+
+#[test]
+fn test_draw_index_bounds() {
+    let env = Env::default();
+    let randomness = BytesN::from_array(&env, &[9u8; 32]);
+
+    assert_eq!(commitment::draw_index(&env, &randomness, 0, 1), 0);
+    for draw in 0..20 {
+        assert!(commitment::draw_index(&env, &randomness, draw, 3) < 3);
+        let large = commitment::draw_index(&env, &randomness, draw, u32::MAX);
+        assert!(large < u32::MAX);
+    }
+
+    // Same randomness and draw always give the same index
+    assert_eq!(
+        commitment::draw_index(&env, &randomness, 5, 1_000),
+        commitment::draw_index(&env, &randomness, 5, 1_000)
+    );
+}
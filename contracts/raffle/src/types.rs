@@ -1,21 +1,84 @@
-use soroban_sdk::{Address, Vec, contracttype};
+use soroban_sdk::{Address, BytesN, Vec, contracttype};
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum State {
     OPEN,      // Accepting ticket purchases
+    CLOSED,    // Sales ended, waiting out close_window before the draw
     DRAWING,   // VRF requested, waiting for callback
-    COMPLETED  // Winner selected and round finished
+    REVEAL,    // Sales closed, committed participants revealing their secrets
+    COMPLETED, // Winner selected and round finished
+    REFUNDING  // Draw abandoned after the VRF never answered, players reclaim their tickets
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrawMode {
+    Vrf,           // Randomness requested from the VRF contract
+    CommitReveal,  // Randomness from participants' revealed secrets, no oracle
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProviderKind {
+    Callback,     // Providers return a request ID and answer later through fulfill_random
+    Synchronous,  // Providers return the random value from the request, the draw settles at once
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeeperBounty {
+    None,              // Nothing paid
+    Fixed(i128),       // Flat amount of the underlying token, capped at the pool
+    BasisPoints(u32),  // Share of the pool at payment time, out of 10_000
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeeperTask {
+    Draw,         // Requested the draw
+    Fulfillment,  // Delivered the randomness that settled the draw
 }
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Config {
-    pub vrf_contract: Address,
+    pub vrf_providers: Vec<Address>,  // Randomness providers requested on every draw
+    pub vrf_threshold: u32,           // Contributions needed to settle a draw
+    pub provider_kind: ProviderKind,  // How providers deliver randomness, read when a draw is requested
     pub underlying_token: Address,
     pub ticket_price: i128,
+    pub price_tiers: Vec<PriceTier>,  // Quantity discounts by ascending min_tickets, empty to always charge ticket_price
+    pub price_curve: PriceCurve,      // How the price rises with the round's tickets sold, only without price_tiers
     pub target_tickets: u32,
-    pub max_tickets_per_participant: u32
+    pub max_total_tickets: u32,  // Hard cap on tickets sold per round, 0 for no cap
+    pub min_participants: u32,   // Distinct participants needed before the round can be drawn
+    pub max_tickets_per_participant: u32,
+    pub draw_mode: DrawMode,
+    pub auto_draw: bool,         // Request the draw from the entry that reaches target_tickets (VRF mode)
+    pub reveal_window: u32,      // Ledgers participants have to reveal in commit-reveal mode
+    pub draw_timeout: u32,       // Ledgers to wait for the VRF before a draw can be retried
+    pub max_draw_attempts: u32,  // Failed VRF requests before the admin can refund the round
+    pub close_window: u32,       // Ledgers between sales closing and the draw, 0 to draw straight from OPEN
+    pub draw_bounty: KeeperBounty,     // Paid from the pool to whoever requests the draw
+    pub fulfill_bounty: KeeperBounty,  // Paid from the pool to whoever triggers the settling fulfillment
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PriceCurve {
+    Flat,                // Every ticket at ticket_price
+    Linear(i128),        // Each ticket sold in the round adds the increment to the next one
+    Stepped(u32, i128),  // Every step_tickets sold add the increment
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceTier {
+    pub min_tickets: u32,    // Smallest purchase the tier applies to
+    pub ticket_price: i128,  // Price per paid ticket in the tier
+    pub bonus_tickets: u32,  // Free tickets for every min_tickets bought, below min_tickets
 }
 
 #[contracttype]
@@ -23,7 +86,27 @@ pub struct Config {
 pub struct Round {
     pub round: u32,
     pub state: State,
-    pub vrf_request_id: Option<u64>,  // VRF request tracking
+    pub vrf_request_id: Option<u64>,  // Request made to the first provider
+    pub draw_seed: Option<BytesN<32>>, // Seed committed to when the draw was requested
+    pub draw_ledger: u32,              // Ledger of the latest VRF request, 0 before the draw
+    pub draw_attempts: u32,            // VRF requests made for this round
+    pub participants_root: Option<BytesN<32>>, // Merkle root over ticket entries, frozen when sales close
+    pub closed_ledger: u32,            // Ledger sales closed on, 0 while open or drawn straight from OPEN
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contribution {
+    pub provider: Address,
+    pub request_id: u64,          // Request made to this provider for the current attempt
+    pub random_value: Option<BytesN<32>>, // Set once the provider answered
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawRandomness {
+    pub threshold: u32,                     // Contributions needed, fixed when the draw was requested
+    pub contributions: Vec<Contribution>,   // One per provider, in provider order
 }
 
 #[contracttype]
@@ -32,6 +115,7 @@ pub struct RoundStats {
     pub total_tickets: u32,
     pub total_participants: u32,
     pub prize_pool: i128,
+    pub keeper_bounties: i128,  // Paid to keepers out of the pool, already taken from prize_pool
 }
 
 #[contracttype]
@@ -41,6 +125,52 @@ pub struct WinnerRecord {
     pub round: u32,
     pub amount: i128,
     pub claimed: bool,
+    pub vrf_request_id: u64,  // VRF request whose randomness settled the round, 0 for commit-reveal
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawRecord {
+    pub seed: BytesN<32>,               // Randomness the winning ticket was drawn from
+    pub winning_ticket: u32,
+    pub total_tickets: u32,
+    pub participants_root: BytesN<32>,  // Round's participants root the ticket was drawn against
+    pub vrf_request_id: u64,            // Request that settled the round, 0 for commit-reveal
+    pub winner: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundSummary {
+    pub participant_count: u32,
+    pub participants_root: BytesN<32>,  // Merkle root over the ticket entries, frozen when sales closed
+    pub pruned_participants: u32,       // Pruning progress through the participant list
+    pub entry_count: u32,
+    pub pruned_entries: u32,            // Pruning progress through the ticket entries
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TicketEntry {
+    pub participant: Address,
+    pub first_ticket: u32,  // Tickets [first_ticket, first_ticket + tickets) of the round
+    pub tickets: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleFrontier {
+    pub leaf_count: u32,
+    pub branch: Vec<BytesN<32>>,  // Left siblings still waiting for a right one, by height
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevealPhase {
+    pub commitments: u32,          // Participants who committed while the round was open
+    pub reveals: u32,              // Participants who revealed so far
+    pub deadline_ledger: u32,      // Last ledger to reveal in, 0 while sales are open
+    pub accumulator: BytesN<32>,   // XOR of every revealed secret
 }
 
 #[contracttype]
@@ -49,17 +179,75 @@ pub struct ParticipantBucket {
     pub participants: Vec<Address>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Liabilities {
+    pub unclaimed_prizes: i128,  // Sum of WinnerRecord amounts not yet claimed
+    pub open_pool: i128,         // Prize pool of the round still selling/drawing
+    pub refunds: i128,           // Pools of refunding rounds not yet reclaimed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryQuote {
+    pub tickets: u32,        // Tickets the purchase gets after the wallet and round caps
+    pub cost: i128,          // Amount charged after price tiers or along the price curve
+    pub marginal_price: i128, // Price of the round's next ticket, before the purchase
+    pub user_tickets: u32,   // Buyer's tickets in the round after the purchase
+    pub total_tickets: u32,  // Round's tickets after the purchase
+    pub odds_bps: u32,       // Buyer's chance to win after the purchase, out of 10_000
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Audit {
+    pub balance: i128,
+    pub liabilities: i128,
+    pub surplus: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeaderboardKind {
+    TopWinners,  // Ranked by total prize amount won
+    TopBuyers,   // Ranked by total tickets bought
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Address,
+    pub total: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub enum Storage {
     Admin,
+    Treasurer,
     Config,
     TotalRounds,
     CurrentRound,                    // Current active round number
     Round(u32),                      // Round data by round number
     RoundStats(u32),                 // Stats for each round
     UserTickets(u32, Address),       // (round, user) -> ticket count
+    UserPaid(u32, Address),          // (round, user) -> amount paid for their tickets
     ParticipantBucket(u32, u32),     // (round, bucket_idx) -> ParticipantBucket
     WinnerRecord(u32),               // round -> WinnerRecord
     UserWinningRounds(Address),      // user -> Vec<u32> of winning rounds
+    UserUnclaimedRounds(Address),    // user -> Vec<u32> of rounds with a prize still to claim
+    RoundSummary(u32),               // round -> RoundSummary kept after pruning
+    Liabilities,                     // Running totals owed to players
+    CurrentSeason,                   // Current leaderboard season number
+    Leaderboard(LeaderboardKind, u32),          // (kind, season) -> Vec<LeaderboardEntry>
+    PlayerTotal(LeaderboardKind, u32, Address), // (kind, season, user) -> running total
+    Commitment(u32, Address),        // (round, user) -> hash committed to on entry
+    Revealed(u32, Address),          // (round, user) -> secret revealed for the commitment
+    RevealPhase(u32),                // round -> RevealPhase
+    Refunded(u32, Address),          // (round, user) -> true once their tickets were refunded
+    RefundedPaid(u32),               // round -> total paid by the players refunded so far
+    DrawRandomness(u32),             // round -> DrawRandomness collected from providers
+    DrawRecord(u32),                 // round -> DrawRecord of the completed draw
+    TicketEntry(u32, u32),           // (round, entry index) -> TicketEntry, one per purchase
+    ParticipantsTree(u32),           // round -> MerkleFrontier over the round's ticket entries
 }
\ No newline at end of file
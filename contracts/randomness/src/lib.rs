@@ -0,0 +1,37 @@
+#![no_std]
+
+//! Interfaces between randomness providers and the contracts consuming their output.
+//!
+//! A provider delivers randomness in one of two ways:
+//! - callback: [`RandomnessProvider::request_random`] opens a request and returns its ID, and
+//!   the provider later calls [`RandomnessConsumer::fulfill_random`] on the requester
+//! - synchronous: [`SyncRandomnessProvider::random_value`] returns the value in the same call
+//!
+//! Providers implement the trait for the mode they support inside their `#[contractimpl]`,
+//! consumers call them through the generated clients.
+
+use soroban_sdk::{contractclient, Address, BytesN, Env};
+
+/// Provider that answers requests later by calling back the requester
+#[contractclient(name = "RandomnessProviderClient")]
+pub trait RandomnessProvider {
+    /// Open a request for randomness bound to `seed` and return its ID.
+    /// The requester must authorize the call.
+    fn request_random(env: Env, requester: Address, seed: BytesN<32>) -> u64;
+}
+
+/// Provider that returns randomness in the same call
+#[contractclient(name = "SyncRandomnessProviderClient")]
+pub trait SyncRandomnessProvider {
+    /// 32 bytes of randomness bound to `seed`. The requester must authorize the call.
+    fn random_value(env: Env, requester: Address, seed: BytesN<32>) -> BytesN<32>;
+}
+
+/// Contract that receives randomness from callback providers
+#[contractclient(name = "RandomnessConsumerClient")]
+pub trait RandomnessConsumer {
+    /// Deliver 32 bytes of randomness answering request `request_id`. `keeper` is the account
+    /// that triggered the delivery, the one a consumer rewards for it.
+    /// The provider must authorize the call.
+    fn fulfill_random(env: Env, provider: Address, request_id: u64, randomness: BytesN<32>, keeper: Address);
+}
@@ -0,0 +1,17 @@
+#[soroban_sdk::contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // Storage Errors
+    AdminNotFound = 401,
+    OracleNotFound = 402,
+    RequestNotFound = 403,
+
+    // Request Errors
+    AlreadyFulfilled = 500,
+    InvalidRandomness = 501,
+
+    // Signed Mode Errors
+    OracleKeyNotSet = 600,
+    SignedFulfillmentRequired = 601,
+}
@@ -0,0 +1,70 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+#[contractevent(topics = ["vrf_v1", "random_requested"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RandomRequested {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub requester: Address,
+    pub seed: BytesN<32>,
+}
+
+#[contractevent(topics = ["vrf_v1", "random_fulfilled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RandomFulfilled {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub requester: Address,
+    pub randomness: BytesN<32>,
+}
+
+#[contractevent(topics = ["vrf_v1", "oracle_changed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleChanged {
+    #[topic]
+    pub oracle: Address,
+}
+
+#[contractevent(topics = ["vrf_v1", "oracle_key_rotated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleKeyRotated {
+    pub public_key: BytesN<32>,
+}
+
+/// Emitted when a contract requests randomness, the oracle listens for this
+pub fn emit_random_requested(env: &Env, request_id: u64, requester: &Address, seed: &BytesN<32>) {
+    RandomRequested {
+        request_id,
+        requester: requester.clone(),
+        seed: seed.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the oracle delivers randomness for a request
+pub fn emit_random_fulfilled(env: &Env, request_id: u64, requester: &Address, randomness: &BytesN<32>) {
+    RandomFulfilled {
+        request_id,
+        requester: requester.clone(),
+        randomness: randomness.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin sets a new oracle
+pub fn emit_oracle_changed(env: &Env, oracle: &Address) {
+    OracleChanged {
+        oracle: oracle.clone(),
+    }
+    .publish(env);
+}
+
+/// Emitted when the admin rotates the oracle signing key
+pub fn emit_oracle_key_rotated(env: &Env, public_key: &BytesN<32>) {
+    OracleKeyRotated {
+        public_key: public_key.clone(),
+    }
+    .publish(env);
+}
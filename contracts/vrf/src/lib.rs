@@ -4,48 +4,202 @@
 //!
 //! Uses env.prng() which is PREDICTABLE and can be manipulated by validators.
 //! For production, use Chainlink VRF, DIA xRandom, or other verifiable randomness.
+//!
+//! Requests are recorded on-chain and only the configured oracle can fulfill them,
+//! each at most once.
+//!
+//! Signed mode: once the admin sets an oracle ed25519 public key, requests can only be
+//! fulfilled with `fulfill_signed`. The oracle signs
+//! `contract xdr || request id (u64 BE) || requester xdr || seed` and the randomness
+//! must equal `sha256(signature)`. This only proves the oracle signed off on the value: a key
+//! holder can produce many valid signatures for the same message and pick the output it
+//! likes, so the oracle is trusted not to bias draws. Use a real VRF or the drand provider
+//! when the output must not be grindable.
+
+use randomness::{RandomnessConsumerClient, RandomnessProvider};
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, contract, contractimpl};
 
-use soroban_sdk::{Address, Env, Symbol, Val, IntoVal, Vec, contract, contractimpl};
+mod error;
+mod events;
+mod storage;
+mod types;
+
+use error::Error;
+use types::{RandomRequest, RequestStatus};
 
 #[contract]
 pub struct MockVRF;
 
 #[contractimpl]
 impl MockVRF {
-    /// Request random number - returns request ID without callback
-    /// In production, an off-chain oracle would listen for this event and call fulfill()
-    pub fn request_random(env: Env, _requester: Address) -> u64 {
-        // Generate a request ID (in production this would be stored/tracked)
-        env.prng().gen()
+    pub fn __constructor(env: &Env, admin: Address, oracle: Address) {
+        storage::set_admin(env, &admin);
+        storage::set_oracle(env, &oracle);
     }
 
-    /// Fulfill a random number request by calling back to the requester
-    /// In production, this would be called by the VRF oracle with verifiable randomness
-    /// In tests/dev, this can be called manually with a generated random number
-    pub fn fulfill(env: Env, requester: Address, random_value: u64) {
-        let callback_args: Vec<Val> = (
-            env.current_contract_address(),
-            random_value,
-        )
-            .into_val(&env);
-
-        let _: Val = env.invoke_contract(
-            &requester,
-            &Symbol::new(&env, "fulfill_random"),
-            callback_args,
-        );
+    /// Fulfill a pending random number request by calling back to the requester
+    /// Only callable by the oracle, only once per request, and only while signed mode is off
+    pub fn fulfill(env: Env, request_id: u64, randomness: BytesN<32>) -> Result<(), Error> {
+        let oracle = storage::get_oracle(&env)?;
+        oracle.require_auth();
+
+        if storage::get_oracle_public_key(&env).is_some() {
+            return Err(Error::SignedFulfillmentRequired);
+        }
+
+        let request = Self::pending_request(&env, request_id)?;
+        Self::deliver(&env, request, &randomness, &oracle);
+        Ok(())
+    }
+
+    /// Fulfill a pending request with randomness signed by the oracle key (anyone can relay).
+    /// The requester sees `relayer` as the keeper that delivered it.
+    pub fn fulfill_signed(
+        env: Env,
+        request_id: u64,
+        randomness: BytesN<32>,
+        signature: BytesN<64>,
+        relayer: Address,
+    ) -> Result<(), Error> {
+        let public_key = storage::get_oracle_public_key(&env).ok_or(Error::OracleKeyNotSet)?;
+        let request = Self::pending_request(&env, request_id)?;
+
+        // Panics if the signature doesn't verify
+        let payload = Self::signing_payload(&env, &request);
+        env.crypto().ed25519_verify(&public_key, &payload, &signature);
+
+        // The output is bound to the signature, not picked by the relayer
+        let expected: BytesN<32> = env
+            .crypto()
+            .sha256(&Bytes::from_array(&env, &signature.to_array()))
+            .into();
+        if randomness != expected {
+            return Err(Error::InvalidRandomness);
+        }
+
+        Self::deliver(&env, request, &randomness, &relayer);
+        Ok(())
+    }
+
+    /// Get a recorded request
+    pub fn get_request(env: Env, request_id: u64) -> Option<RandomRequest> {
+        storage::get_request(&env, request_id)
+    }
+
+    /// Get the oracle allowed to fulfill requests
+    pub fn oracle(env: Env) -> Result<Address, Error> {
+        storage::get_oracle(&env)
     }
 
-    /// Get a random number (utility for testing)
-    pub fn get_random(env: Env) -> u64 {
+    /// Set a new oracle. Only callable by admin.
+    pub fn set_oracle(env: Env, oracle: Address) -> Result<(), Error> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        storage::set_oracle(&env, &oracle);
+        events::emit_oracle_changed(&env, &oracle);
+        storage::extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Get the oracle signing key, set when signed mode is on
+    pub fn oracle_public_key(env: Env) -> Option<BytesN<32>> {
+        storage::get_oracle_public_key(&env)
+    }
+
+    /// Set or rotate the oracle signing key, turning on signed mode. Only callable by admin.
+    pub fn set_oracle_public_key(env: Env, public_key: BytesN<32>) -> Result<(), Error> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        storage::set_oracle_public_key(&env, &public_key);
+        events::emit_oracle_key_rotated(&env, &public_key);
+        storage::extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Get 32 random bytes (utility for testing)
+    pub fn get_random(env: Env) -> BytesN<32> {
         env.prng().gen()
     }
+
+    /// Message the oracle signs for a request
+    pub fn get_signing_payload(env: Env, request_id: u64) -> Result<Bytes, Error> {
+        let request = storage::get_request(&env, request_id).ok_or(Error::RequestNotFound)?;
+        Ok(Self::signing_payload(&env, &request))
+    }
+}
+
+#[contractimpl]
+impl RandomnessProvider for MockVRF {
+    /// Request random number - records the request and returns its ID without callback
+    /// An off-chain oracle listens for the RandomRequested event and calls fulfill()
+    fn request_random(env: Env, requester: Address, seed: BytesN<32>) -> u64 {
+        // Only the requester itself can open a request on its behalf
+        requester.require_auth();
+
+        let request = RandomRequest {
+            id: storage::next_request_id(&env),
+            requester: requester.clone(),
+            seed: seed.clone(),
+            ledger: env.ledger().sequence(),
+            status: RequestStatus::PENDING,
+        };
+        storage::set_request(&env, &request);
+
+        events::emit_random_requested(&env, request.id, &requester, &seed);
+
+        storage::extend_instance_ttl(&env);
+        request.id
+    }
+}
+
+impl MockVRF {
+    fn pending_request(env: &Env, request_id: u64) -> Result<RandomRequest, Error> {
+        let request = storage::get_request(env, request_id).ok_or(Error::RequestNotFound)?;
+        if request.status != RequestStatus::PENDING {
+            return Err(Error::AlreadyFulfilled);
+        }
+        Ok(request)
+    }
+
+    /// contract xdr || request id (u64 BE) || requester xdr || seed
+    fn signing_payload(env: &Env, request: &RandomRequest) -> Bytes {
+        let mut payload = env.current_contract_address().to_xdr(env);
+        payload.extend_from_array(&request.id.to_be_bytes());
+        payload.append(&request.requester.clone().to_xdr(env));
+        payload.append(&Bytes::from_array(env, &request.seed.to_array()));
+        payload
+    }
+
+    /// Mark the request fulfilled and call back to the requester
+    fn deliver(env: &Env, mut request: RandomRequest, randomness: &BytesN<32>, keeper: &Address) {
+        // Mark fulfilled before calling out so the request can't be replayed
+        request.status = RequestStatus::FULFILLED;
+        storage::set_request(env, &request);
+
+        events::emit_random_fulfilled(env, request.id, &request.requester, randomness);
+
+        // The request ID lets the requester match the answer to what it asked for
+        RandomnessConsumerClient::new(env, &request.requester).fulfill_random(
+            &env.current_contract_address(),
+            &request.id,
+            randomness,
+            keeper,
+        );
+
+        storage::extend_instance_ttl(env);
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{Env, symbol_short};
+    use ed25519_dalek::{Signer, SigningKey};
+    use hex_literal::hex;
+    use soroban_sdk::{Env, symbol_short, testutils::Address as _};
+
+    // RFC 8032 section 7.1, test 1
+    const ORACLE_SECRET: [u8; 32] = hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    const ORACLE_PUBLIC: [u8; 32] = hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
 
     // Mock raffle contract for testing VRF callbacks
     #[contract]
@@ -53,36 +207,66 @@ mod test {
 
     #[contractimpl]
     impl MockRaffleContract {
-        pub fn fulfill_random(_env: Env, _vrf: Address, random_value: u64) -> u64 {
-            // Store the random value in storage for verification
-            _env.storage()
+        pub fn fulfill_random(env: Env, _vrf: Address, request_id: u64, randomness: BytesN<32>, keeper: Address) {
+            // Store the request ID and randomness in storage for verification
+            env.storage()
                 .instance()
-                .set(&symbol_short!("random"), &random_value);
-            random_value
+                .set(&symbol_short!("random"), &(request_id, randomness));
+            env.storage().instance().set(&symbol_short!("keeper"), &keeper);
         }
 
-        pub fn get_stored_random(env: Env) -> Option<u64> {
+        pub fn get_stored_random(env: Env) -> Option<(u64, BytesN<32>)> {
             env.storage().instance().get(&symbol_short!("random"))
         }
+
+        pub fn get_keeper(env: Env) -> Option<Address> {
+            env.storage().instance().get(&symbol_short!("keeper"))
+        }
+    }
+
+    fn setup<'a>(env: &Env) -> (MockVRFClient<'a>, Address, Address) {
+        let admin = Address::generate(env);
+        let oracle = Address::generate(env);
+        let vrf_id = env.register(MockVRF, (&admin, &oracle));
+        (MockVRFClient::new(env, &vrf_id), admin, oracle)
+    }
+
+    fn seed(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[7u8; 32])
+    }
+
+    fn random_bytes(env: &Env, byte: u8) -> BytesN<32> {
+        BytesN::from_array(env, &[byte; 32])
+    }
+
+    /// Sign a request's payload with the test oracle key, returning (randomness, signature)
+    fn sign_request(env: &Env, client: &MockVRFClient, request_id: u64) -> (BytesN<32>, BytesN<64>) {
+        let payload = client.get_signing_payload(&request_id);
+        let mut message = [0u8; 256];
+        let len = payload.len() as usize;
+        payload.copy_into_slice(&mut message[..len]);
+
+        let signature = SigningKey::from_bytes(&ORACLE_SECRET).sign(&message[..len]).to_bytes();
+        let randomness: BytesN<32> = env.crypto().sha256(&Bytes::from_array(env, &signature)).into();
+        (randomness, BytesN::from_array(env, &signature))
     }
 
     #[test]
     fn test_get_random_works() {
         let env = Env::default();
-        let contract_id = env.register(MockVRF, ());
-        let client = MockVRFClient::new(&env, &contract_id);
+        let (client, _, _) = setup(&env);
 
         let random1 = client.get_random();
         let random2 = client.get_random();
 
-        assert!(random1 > 0 || random2 > 0);
+        assert_eq!(random1.len(), 32);
+        assert!(random1 != random2);
     }
 
     #[test]
     fn test_get_random_generates_different_values() {
         let env = Env::default();
-        let contract_id = env.register(MockVRF, ());
-        let client = MockVRFClient::new(&env, &contract_id);
+        let (client, _, _) = setup(&env);
 
         let random1 = client.get_random();
         let random2 = client.get_random();
@@ -97,35 +281,224 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let vrf_id = env.register(MockVRF, ());
-        let vrf_client = MockVRFClient::new(&env, &vrf_id);
-
+        let (vrf_client, _, _) = setup(&env);
         let raffle_id = env.register(MockRaffleContract, ());
 
         // Request random number - should return a request ID without callback
-        let request_id = vrf_client.request_random(&raffle_id);
+        let request_id = vrf_client.request_random(&raffle_id, &seed(&env));
 
         // Verify we got a request ID
         assert!(request_id > 0);
     }
 
     #[test]
-    fn test_fulfill_calls_callback() {
+    fn test_request_random_records_request() {
         let env = Env::default();
         env.mock_all_auths();
 
-        let vrf_id = env.register(MockVRF, ());
-        let vrf_client = MockVRFClient::new(&env, &vrf_id);
+        let (vrf_client, _, _) = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+
+        let first = vrf_client.request_random(&raffle_id, &seed(&env));
+        let second = vrf_client.request_random(&raffle_id, &seed(&env));
+        assert_eq!(second, first + 1);
+
+        let request = vrf_client.get_request(&first).unwrap();
+        assert_eq!(request.id, first);
+        assert_eq!(request.requester, raffle_id);
+        assert_eq!(request.seed, seed(&env));
+        assert_eq!(request.ledger, env.ledger().sequence());
+        assert_eq!(request.status, RequestStatus::PENDING);
+
+        assert!(vrf_client.get_request(&99).is_none());
+    }
 
+    #[test]
+    fn test_fulfill_calls_callback() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (vrf_client, _, oracle) = setup(&env);
         let raffle_id = env.register(MockRaffleContract, ());
         let raffle_client = MockRaffleContractClient::new(&env, &raffle_id);
 
         // Generate a random number and fulfill manually
-        let random_value = vrf_client.get_random();
-        vrf_client.fulfill(&raffle_id, &random_value);
+        let request_id = vrf_client.request_random(&raffle_id, &seed(&env));
+        let randomness = vrf_client.get_random();
+        vrf_client.fulfill(&request_id, &randomness);
 
-        // Verify the callback was invoked with the correct value
+        // Verify the callback was invoked with the request ID and correct value
         let stored = raffle_client.get_stored_random();
-        assert_eq!(stored, Some(random_value));
+        assert_eq!(stored, Some((request_id, randomness)));
+        assert_eq!(raffle_client.get_keeper(), Some(oracle));
+        assert_eq!(vrf_client.get_request(&request_id).unwrap().status, RequestStatus::FULFILLED);
+    }
+
+    #[test]
+    fn test_fulfill_only_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (vrf_client, _, _) = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+
+        let request_id = vrf_client.request_random(&raffle_id, &seed(&env));
+        vrf_client.fulfill(&request_id, &random_bytes(&env, 1));
+
+        assert_eq!(vrf_client.try_fulfill(&request_id, &random_bytes(&env, 2)), Err(Ok(Error::AlreadyFulfilled)));
+        assert_eq!(vrf_client.try_fulfill(&99, &random_bytes(&env, 2)), Err(Ok(Error::RequestNotFound)));
+    }
+
+    #[test]
+    fn test_fulfill_requires_oracle() {
+        let env = Env::default();
+        let (vrf_client, _, oracle) = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+
+        env.mock_all_auths();
+        let request_id = vrf_client.request_random(&raffle_id, &seed(&env));
+
+        // No auths: a random caller can't pick the value
+        env.set_auths(&[]);
+        assert!(vrf_client.try_fulfill(&request_id, &random_bytes(&env, 1)).is_err());
+
+        // The oracle can
+        env.mock_all_auths();
+        vrf_client.fulfill(&request_id, &random_bytes(&env, 1));
+        assert_eq!(env.auths()[0].0, oracle);
+    }
+
+    #[test]
+    fn test_set_oracle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (vrf_client, admin, _) = setup(&env);
+        let new_oracle = Address::generate(&env);
+        vrf_client.set_oracle(&new_oracle);
+        assert_eq!(env.auths()[0].0, admin);
+
+        assert_eq!(vrf_client.oracle(), new_oracle);
+    }
+
+    #[test]
+    fn test_rfc8032_key_vector() {
+        let signing_key = SigningKey::from_bytes(&ORACLE_SECRET);
+        assert_eq!(signing_key.verifying_key().to_bytes(), ORACLE_PUBLIC);
+
+        // Empty message signature from the RFC, checked by the host
+        let env = Env::default();
+        let signature = signing_key.sign(&[]).to_bytes();
+        assert_eq!(
+            signature,
+            hex!("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b")
+        );
+        env.crypto().ed25519_verify(
+            &BytesN::from_array(&env, &ORACLE_PUBLIC),
+            &Bytes::new(&env),
+            &BytesN::from_array(&env, &signature),
+        );
+    }
+
+    #[test]
+    fn test_fulfill_signed() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (vrf_client, _, _) = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+        let raffle_client = MockRaffleContractClient::new(&env, &raffle_id);
+        vrf_client.set_oracle_public_key(&BytesN::from_array(&env, &ORACLE_PUBLIC));
+
+        let request_id = vrf_client.request_random(&raffle_id, &seed(&env));
+        let (randomness, signature) = sign_request(&env, &vrf_client, request_id);
+
+        // Anyone can relay a signed answer and is passed on as the keeper
+        let relayer = Address::generate(&env);
+        env.set_auths(&[]);
+        vrf_client.fulfill_signed(&request_id, &randomness, &signature, &relayer);
+
+        assert_eq!(raffle_client.get_stored_random(), Some((request_id, randomness.clone())));
+        assert_eq!(raffle_client.get_keeper(), Some(relayer.clone()));
+        assert_eq!(vrf_client.get_request(&request_id).unwrap().status, RequestStatus::FULFILLED);
+
+        assert_eq!(
+            vrf_client.try_fulfill_signed(&request_id, &randomness, &signature, &relayer),
+            Err(Ok(Error::AlreadyFulfilled))
+        );
+    }
+
+    #[test]
+    fn test_fulfill_signed_rejects_bad_inputs() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (vrf_client, _, _) = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+        let first = vrf_client.request_random(&raffle_id, &seed(&env));
+        let second = vrf_client.request_random(&raffle_id, &seed(&env));
+        let (randomness, signature) = sign_request(&env, &vrf_client, first);
+        let relayer = Address::generate(&env);
+
+        assert_eq!(
+            vrf_client.try_fulfill_signed(&first, &randomness, &signature, &relayer),
+            Err(Ok(Error::OracleKeyNotSet))
+        );
+        vrf_client.set_oracle_public_key(&BytesN::from_array(&env, &ORACLE_PUBLIC));
+
+        // Randomness not derived from the signature
+        let chosen = BytesN::from_array(&env, &[1u8; 32]);
+        assert_eq!(
+            vrf_client.try_fulfill_signed(&first, &chosen, &signature, &relayer),
+            Err(Ok(Error::InvalidRandomness))
+        );
+
+        // Signature for a different request
+        assert!(vrf_client.try_fulfill_signed(&second, &randomness, &signature, &relayer).is_err());
+
+        // Signature from a different key
+        let other_key = SigningKey::from_bytes(&[42u8; 32]);
+        let payload = vrf_client.get_signing_payload(&first);
+        let mut message = [0u8; 256];
+        let len = payload.len() as usize;
+        payload.copy_into_slice(&mut message[..len]);
+        let forged = other_key.sign(&message[..len]).to_bytes();
+        let forged_randomness: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, &forged)).into();
+        assert!(vrf_client
+            .try_fulfill_signed(&first, &forged_randomness, &BytesN::from_array(&env, &forged), &relayer)
+            .is_err());
+
+        assert_eq!(vrf_client.get_request(&first).unwrap().status, RequestStatus::PENDING);
+    }
+
+    #[test]
+    fn test_signed_mode_disables_unsigned_fulfill() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (vrf_client, _, _) = setup(&env);
+        let raffle_id = env.register(MockRaffleContract, ());
+        let request_id = vrf_client.request_random(&raffle_id, &seed(&env));
+
+        vrf_client.set_oracle_public_key(&BytesN::from_array(&env, &ORACLE_PUBLIC));
+        assert_eq!(
+            vrf_client.try_fulfill(&request_id, &random_bytes(&env, 1)),
+            Err(Ok(Error::SignedFulfillmentRequired))
+        );
+    }
+
+    #[test]
+    fn test_set_oracle_public_key_requires_admin() {
+        let env = Env::default();
+        let (vrf_client, admin, _) = setup(&env);
+        let public_key = BytesN::from_array(&env, &ORACLE_PUBLIC);
+
+        assert!(vrf_client.try_set_oracle_public_key(&public_key).is_err());
+        assert_eq!(vrf_client.oracle_public_key(), None);
+
+        env.mock_all_auths();
+        vrf_client.set_oracle_public_key(&public_key);
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(vrf_client.oracle_public_key(), Some(public_key));
     }
 }
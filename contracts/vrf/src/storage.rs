@@ -0,0 +1,69 @@
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::{error::Error, types::{RandomRequest, Storage}};
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+// Admin
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&Storage::Admin, admin);
+}
+
+pub fn get_admin(env: &Env) -> Result<Address, Error> {
+    env.storage().instance().get(&Storage::Admin).ok_or(Error::AdminNotFound)
+}
+
+// Oracle
+pub fn set_oracle(env: &Env, oracle: &Address) {
+    env.storage().instance().set(&Storage::Oracle, oracle);
+}
+
+pub fn get_oracle(env: &Env) -> Result<Address, Error> {
+    env.storage().instance().get(&Storage::Oracle).ok_or(Error::OracleNotFound)
+}
+
+// Oracle Public Key
+pub fn set_oracle_public_key(env: &Env, public_key: &BytesN<32>) {
+    env.storage().instance().set(&Storage::OraclePublicKey, public_key);
+}
+
+pub fn get_oracle_public_key(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&Storage::OraclePublicKey)
+}
+
+// Requests
+pub fn next_request_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&Storage::NextRequestId).unwrap_or(1);
+    env.storage().instance().set(&Storage::NextRequestId, &(id + 1));
+    id
+}
+
+pub fn get_request(env: &Env, id: u64) -> Option<RandomRequest> {
+    let key = Storage::Request(id);
+    let request = env.storage().persistent().get(&key);
+    if request.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    request
+}
+
+pub fn set_request(env: &Env, request: &RandomRequest) {
+    let key = Storage::Request(request.id);
+    env.storage().persistent().set(&key, request);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
@@ -0,0 +1,29 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum RequestStatus {
+    PENDING,    // Waiting for the oracle to fulfill
+    FULFILLED,  // Randomness delivered to the requester
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RandomRequest {
+    pub id: u64,
+    pub requester: Address,
+    pub seed: BytesN<32>,
+    pub ledger: u32,           // Ledger sequence the request was made in
+    pub status: RequestStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Storage {
+    Admin,
+    Oracle,           // Address allowed to fulfill requests
+    OraclePublicKey,  // ed25519 key whose signatures fulfill requests in signed mode
+    NextRequestId,
+    Request(u64),     // request id -> RandomRequest
+}
@@ -20,11 +20,12 @@ import { getRoundStats } from "./raffle/get_round_stats.js";
 import { isReadyToDraw } from "./raffle/is_ready_to_draw.js";
 import { enterRaffle } from "./raffle/enter.js";
 import { requestDraw } from "./raffle/request_draw.js";
+import { closeSales } from "./raffle/close_sales.js";
 import { getRandomNumber } from "./vrf/get_random.js";
 import { fulfillVRF } from "./vrf/fulfill.js";
 import { getRoundWinner } from "./raffle/get_winner.js";
 import { claimPrize } from "./raffle/claim_prize.js";
-import { CONTRACTS, RAFFLE_CONFIG } from "./utils/contracts.js";
+import { RAFFLE_CONFIG } from "./utils/contracts.js";
 
 // Bot configuration
 const LOOP_INTERVAL = 30_000; // Check every 30 seconds
@@ -37,6 +38,7 @@ const DRAW_DELAY_MAX = 120_000; // Wait 2 minutes before drawing
 let lastTicketPurchaseTime = 0;
 let drawDelayStartTime = 0;
 let lastRoundCompleted = 0;
+let lastRequestFulfilled: bigint | null = null;
 
 // Statistics
 const stats = {
@@ -168,9 +170,10 @@ async function handleReadyToDraw(
     // Immediately fulfill VRF (simulating oracle)
     log(`🔮 Fulfilling VRF...`);
     const randomValue = await getRandomNumber();
-    await fulfillVRF(CONTRACTS.RAFFLE, randomValue);
+    await fulfillVRF(vrfRequestId, randomValue);
+    lastRequestFulfilled = vrfRequestId;
 
-    log(`  ✓ VRF fulfilled with random value: ${randomValue}`);
+    log(`  ✓ VRF fulfilled with randomness: ${randomValue.toString("hex")}`);
 
     // Reset draw delay timer
     drawDelayStartTime = 0;
@@ -258,15 +261,46 @@ async function botLoop(walletManager: WalletManager): Promise<void> {
 
           if (ready) {
             await handleReadyToDraw(walletManager, currentRound);
+          } else if (
+            roundStats.total_tickets >= RAFFLE_CONFIG.TARGET_TICKETS
+          ) {
+            // Target met but the raffle has a close window, end sales first
+            try {
+              await closeSales(walletManager.getRandomWallet());
+            } catch (error) {
+              log(`  ✗ Could not close sales: ${error}`);
+            }
           } else {
             await handleOpenState(walletManager, currentRound, roundStats);
           }
           break;
         }
 
-        case "DRAWING":
-          log(`⌛ Draw in progress, waiting for completion...`);
+        case "CLOSED": {
+          const ready = await isReadyToDraw();
+
+          if (ready) {
+            await handleReadyToDraw(walletManager, currentRound);
+          } else {
+            log(`🔒 Sales closed, waiting out the close window...`);
+          }
           break;
+        }
+
+        case "DRAWING": {
+          // With auto_draw the target entry requested the draw, fulfill it here
+          const requestId = roundInfo.vrf_request_id;
+          if (requestId != null && requestId !== lastRequestFulfilled) {
+            log(`🔮 Fulfilling VRF request ${requestId}...`);
+            const randomValue = await getRandomNumber();
+            await fulfillVRF(requestId, randomValue);
+            lastRequestFulfilled = requestId;
+            stats.rafflesDrawn++;
+          } else {
+            log(`⌛ Draw in progress, waiting for completion...`);
+          }
+          break;
+        }
 
         case "COMPLETED":
           await handleCompletedState(walletManager, currentRound);
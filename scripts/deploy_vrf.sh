@@ -54,13 +54,20 @@ fi
 echo -e "${GREEN}✓ Published to registry${NC}"
 echo ""
 
-# Step 3: Deploy instance (VRF has no constructor parameters)
+# Step 3: Deploy instance (admin is the source account, oracle defaults to it too)
+ADMIN_ADDRESS=$(stellar keys address ${SOURCE_ACCOUNT})
+ORACLE_ADDRESS="${ORACLE_ADDRESS:-$ADMIN_ADDRESS}"
 echo -e "${YELLOW}Step 3: Deploying VRF contract instance...${NC}"
+echo -e "Admin: ${YELLOW}${ADMIN_ADDRESS}${NC}"
+echo -e "Oracle: ${YELLOW}${ORACLE_ADDRESS}${NC}"
 stellar registry deploy \
     --contract-name ${CONTRACT_NAME} \
     --wasm-name ${WASM_NAME} \
     --source-account ${SOURCE_ACCOUNT} \
-    --network ${NETWORK}
+    --network ${NETWORK} \
+    -- \
+    --admin ${ADMIN_ADDRESS} \
+    --oracle ${ORACLE_ADDRESS}
 
 if [ $? -ne 0 ]; then
     echo -e "${RED}Deployment failed!${NC}"
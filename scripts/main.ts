@@ -167,9 +167,11 @@ async function main() {
 
   const randomValue = await getRandomNumber();
 
-  console.log(`Fulfilling VRF request with random value: ${randomValue}\n`);
+  console.log(
+    `Fulfilling VRF request with randomness: ${randomValue.toString("hex")}\n`,
+  );
 
-  await fulfillVRF(CONTRACTS.RAFFLE, randomValue);
+  await fulfillVRF(vrfRequestId, randomValue);
 
   console.log("\n✓ Oracle fulfilled VRF successfully\n");
 
@@ -0,0 +1,46 @@
+import { Contract, Keypair, scValToNative } from "@stellar/stellar-sdk";
+import { CONTRACTS } from "../utils/contracts.js";
+import {
+  createAndFundAccount,
+  sendTransaction,
+  waitForTransaction,
+} from "../utils/stellar.js";
+
+/**
+ * Close sales on the current raffle round, starting its close window
+ * This can be called by anyone once the round can be drawn
+ */
+export async function closeSales(callerKeypair?: Keypair): Promise<number> {
+  const raffleContract = new Contract(CONTRACTS.RAFFLE);
+
+  // Create and fund caller account if not provided
+  const caller = callerKeypair || (await createAndFundAccount());
+
+  console.log(`Closing sales...`);
+  console.log(`  Caller: ${caller.publicKey()}`);
+
+  const txHash = await sendTransaction(raffleContract, "close_sales", caller);
+
+  const txResponse = await waitForTransaction(txHash);
+
+  // Extract the first ledger a draw can be requested on
+  let drawLedger = 0;
+  if ("returnValue" in txResponse && txResponse.returnValue) {
+    drawLedger = Number(scValToNative(txResponse.returnValue));
+  }
+
+  console.log(`✓ Sales closed`);
+  console.log(`  Draw allowed from ledger: ${drawLedger}`);
+
+  return drawLedger;
+}
+
+// If running directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  closeSales()
+    .then(() => process.exit(0))
+    .catch((err) => {
+      console.error(err);
+      process.exit(1);
+    });
+}
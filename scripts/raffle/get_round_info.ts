@@ -11,8 +11,9 @@ import { simulateReadOnly } from "../utils/stellar.js";
 
 interface RoundInfo {
   round: number;
-  state: "OPEN" | "DRAWING" | "COMPLETED";
+  state: "OPEN" | "CLOSED" | "DRAWING" | "REVEAL" | "COMPLETED" | "REFUNDING";
   vrf_request_id: bigint | null;
+  closed_ledger: number;
 }
 
 /**
@@ -38,6 +39,7 @@ export async function getRoundInfo(roundNumber: number): Promise<RoundInfo> {
     round: parsed.round,
     state: Array.isArray(parsed.state) ? parsed.state[0] : parsed.state,
     vrf_request_id: parsed.vrf_request_id,
+    closed_ledger: parsed.closed_ledger,
   };
 
   console.log(`✓ Round Info:`, roundInfo);
@@ -11,7 +11,7 @@ import { isReadyToDraw } from "./is_ready_to_draw.js";
 import { requestDraw } from "./request_draw.js";
 import { getRandomNumber } from "../vrf/get_random.js";
 import { fulfillVRF } from "../vrf/fulfill.js";
-import { CONTRACTS, RAFFLE_CONFIG } from "../utils/contracts.js";
+import { RAFFLE_CONFIG } from "../utils/contracts.js";
 import { claimPrize } from "./claim_prize.js";
 import { getCurrentRound } from "./get_current_round.js";
 import { getRoundWinner } from "./get_winner.js";
@@ -75,7 +75,7 @@ async function main() {
   // Step 5: Request draw
   console.log("Step 5: Requesting draw...\n");
 
-  await requestDraw(users[0]);
+  const requestId = await requestDraw(users[0]);
 
   console.log("\n");
 
@@ -84,7 +84,7 @@ async function main() {
 
   const randomValue = await getRandomNumber();
 
-  await fulfillVRF(CONTRACTS.RAFFLE, randomValue);
+  await fulfillVRF(requestId, randomValue);
 
   console.log("\n");
 
@@ -0,0 +1,65 @@
+import {
+  Contract,
+  nativeToScVal,
+  scValToNative,
+  xdr,
+} from "@stellar/stellar-sdk";
+import { CONTRACTS } from "../utils/contracts.js";
+import { simulateReadOnly } from "../utils/stellar.js";
+
+interface EntryQuote {
+  tickets: number;
+  cost: bigint;
+  marginal_price: bigint;
+  user_tickets: number;
+  total_tickets: number;
+  odds_bps: number;
+}
+
+/**
+ * Quote what buying tickets in the current round would get a user
+ */
+export async function quoteEntry(
+  user: string,
+  numTickets: number,
+): Promise<EntryQuote> {
+  const raffleContract = new Contract(CONTRACTS.RAFFLE);
+
+  console.log(`Quoting ${numTickets} ticket(s) for ${user}...`);
+
+  const args: xdr.ScVal[] = [
+    nativeToScVal(user, { type: "address" }),
+    nativeToScVal(numTickets, { type: "u32" }),
+  ];
+
+  const result = await simulateReadOnly<xdr.ScVal>(
+    raffleContract,
+    "quote_entry",
+    ...args,
+  );
+
+  const quote = scValToNative(result) as EntryQuote;
+
+  console.log(`✓ Quote:`);
+  console.log(`  Tickets: ${quote.tickets}`);
+  console.log(`  Cost: ${quote.cost}`);
+  console.log(`  Next ticket price: ${quote.marginal_price}`);
+  console.log(
+    `  Odds: ${quote.user_tickets}/${quote.total_tickets} (${quote.odds_bps / 100}%)`,
+  );
+
+  return quote;
+}
+
+// If running directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  const user = process.argv[2];
+  const numTickets = parseInt(process.argv[3] || "1");
+
+  quoteEntry(user, numTickets)
+    .then(() => process.exit(0))
+    .catch((err) => {
+      console.error(err);
+      process.exit(1);
+    });
+}
@@ -1,4 +1,4 @@
-import { Contract, Keypair, scValToNative } from "@stellar/stellar-sdk";
+import { Contract, Keypair, nativeToScVal, scValToNative } from "@stellar/stellar-sdk";
 import { CONTRACTS } from "../utils/contracts.js";
 import {
   createAndFundAccount,
@@ -8,7 +8,8 @@ import {
 
 /**
  * Request a draw for the current raffle round
- * This can be called by anyone once the target participants is met
+ * This can be called by anyone once the target participants is met, the caller
+ * receives any draw bounty configured on the raffle
  */
 export async function requestDraw(callerKeypair?: Keypair): Promise<bigint> {
   const raffleContract = new Contract(CONTRACTS.RAFFLE);
@@ -19,7 +20,12 @@ export async function requestDraw(callerKeypair?: Keypair): Promise<bigint> {
   console.log(`Requesting draw...`);
   console.log(`  Caller: ${caller.publicKey()}`);
 
-  const txHash = await sendTransaction(raffleContract, "request_draw", caller);
+  const txHash = await sendTransaction(
+    raffleContract,
+    "request_draw",
+    caller,
+    nativeToScVal(caller.publicKey(), { type: "address" }),
+  );
 
   const txResponse = await waitForTransaction(txHash);
 
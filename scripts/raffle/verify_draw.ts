@@ -0,0 +1,70 @@
+import {
+  Contract,
+  nativeToScVal,
+  scValToNative,
+  xdr,
+} from "@stellar/stellar-sdk";
+import { CONTRACTS } from "../utils/contracts.js";
+import { simulateReadOnly } from "../utils/stellar.js";
+
+interface DrawRecord {
+  seed: Buffer;
+  winning_ticket: number;
+  total_tickets: number;
+  participants_root: Buffer;
+  vrf_request_id: bigint;
+  winner: string;
+}
+
+/**
+ * Recompute a completed round's winner on-chain and compare it with the stored draw record
+ */
+export async function verifyDraw(round: number): Promise<boolean> {
+  const raffleContract = new Contract(CONTRACTS.RAFFLE);
+  const roundArg = nativeToScVal(round, { type: "u32" });
+
+  console.log(`Verifying Round ${round} draw...`);
+
+  const recordResult = await simulateReadOnly<xdr.ScVal>(
+    raffleContract,
+    "get_draw_record",
+    roundArg,
+  );
+  const record = scValToNative(recordResult) as DrawRecord | undefined;
+  if (!record) {
+    throw new Error(`Round ${round} has no draw record`);
+  }
+
+  console.log(`  Seed: ${record.seed.toString("hex")}`);
+  console.log(
+    `  Winning ticket: ${record.winning_ticket} of ${record.total_tickets}`,
+  );
+  console.log(
+    `  Participants root: ${record.participants_root.toString("hex")}`,
+  );
+  console.log(`  Request ID: ${record.vrf_request_id}`);
+  console.log(`  Winner: ${record.winner}`);
+
+  const verifyResult = await simulateReadOnly<xdr.ScVal>(
+    raffleContract,
+    "verify_draw",
+    roundArg,
+  );
+  const verified = scValToNative(verifyResult) as boolean;
+
+  console.log(verified ? `✓ Draw verified` : `✗ Draw does not match`);
+
+  return verified;
+}
+
+// If running directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  const round = process.argv[2];
+
+  verifyDraw(Number(round))
+    .then(() => process.exit(0))
+    .catch((err) => {
+      console.error(err);
+      process.exit(1);
+    });
+}
@@ -1,3 +1,4 @@
+import { randomBytes } from "node:crypto";
 import { Contract, Keypair, nativeToScVal, xdr } from "@stellar/stellar-sdk";
 import { CONTRACTS } from "../utils/contracts.js";
 import {
@@ -7,26 +8,31 @@ import {
 } from "../utils/stellar.js";
 
 /**
- * Fulfill a VRF request by calling back to the requester contract
- * This simulates what the oracle would do in production
+ * Fulfill a VRF request, the VRF contract then calls back to the requester contract
+ * This simulates what the oracle would do in production. Only the oracle
+ * configured on the VRF contract can fulfill, pass it in or set ORACLE_SECRET.
  */
 export async function fulfillVRF(
-  requesterAddress: string,
-  randomValue: bigint,
+  requestId: bigint,
+  randomness: Buffer,
   oracleSigner?: Keypair,
 ): Promise<void> {
   const vrfContract = new Contract(CONTRACTS.VRF);
 
-  // Create and fund oracle account if not provided
-  const oracle = oracleSigner || (await createAndFundAccount());
+  const oracleSecret = process.env.ORACLE_SECRET;
+  const oracle =
+    oracleSigner ||
+    (oracleSecret
+      ? Keypair.fromSecret(oracleSecret)
+      : await createAndFundAccount());
 
   console.log(`Fulfilling VRF request...`);
-  console.log(`  Requester: ${requesterAddress}`);
-  console.log(`  Random Value: ${randomValue}`);
+  console.log(`  Request ID: ${requestId}`);
+  console.log(`  Randomness: ${randomness.toString("hex")}`);
 
   const args: xdr.ScVal[] = [
-    nativeToScVal(requesterAddress, { type: "address" }),
-    nativeToScVal(randomValue, { type: "u64" }),
+    nativeToScVal(requestId, { type: "u64" }),
+    nativeToScVal(randomness),
   ];
 
   const txHash = await sendTransaction(vrfContract, "fulfill", oracle, ...args);
@@ -38,12 +44,13 @@ export async function fulfillVRF(
 
 // If running directly
 if (import.meta.url === `file://${process.argv[1]}`) {
-  const requester = process.argv[2] || CONTRACTS.RAFFLE;
-  const randomValue = BigInt(
-    process.argv[3] || Math.floor(Math.random() * Number.MAX_SAFE_INTEGER),
-  );
+  const requestId = BigInt(process.argv[2]);
+  // 32 bytes of randomness as hex, random if not given
+  const randomness = process.argv[3]
+    ? Buffer.from(process.argv[3], "hex")
+    : randomBytes(32);
 
-  fulfillVRF(requester, randomValue)
+  fulfillVRF(requestId, randomness)
     .then(() => process.exit(0))
     .catch((err) => {
       console.error(err);
@@ -3,18 +3,18 @@ import { CONTRACTS } from "../utils/contracts.js";
 import { simulateReadOnly } from "../utils/stellar.js";
 
 /**
- * Get a random number from the VRF contract (read-only simulation)
+ * Get 32 random bytes from the VRF contract (read-only simulation)
  */
-export async function getRandomNumber(): Promise<bigint> {
+export async function getRandomNumber(): Promise<Buffer> {
   const vrfContract = new Contract(CONTRACTS.VRF);
 
   console.log("Getting random number from VRF...");
 
   const result = await simulateReadOnly<xdr.ScVal>(vrfContract, "get_random");
 
-  const randomValue = scValToNative(result) as bigint;
+  const randomValue = scValToNative(result) as Buffer;
 
-  console.log(`✓ Random number: ${randomValue}`);
+  console.log(`✓ Randomness: ${randomValue.toString("hex")}`);
 
   return randomValue;
 }
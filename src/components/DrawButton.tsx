@@ -120,13 +120,13 @@ export const DrawButton = () => {
         RAFFLE_CONTRACT,
         "request_draw",
         address!,
-        ...[],
+        ...[nativeToScVal(address!, { type: "address" })],
       );
       await waitForTransaction(requestDrawTxHash);
 
       // Step 2: Auto-fulfill VRF (simulates oracle)
       setDrawStep("Fulfilling VRF (simulating oracle)...");
-      await autoFulfillVRF();
+      await autoFulfillVRF(currentRound);
 
       // Step 3: Wait for draw completion
       setDrawStep("Waiting for winner selection...");
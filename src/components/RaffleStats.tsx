@@ -114,10 +114,16 @@ export const RaffleStats = () => {
     switch (state) {
       case "OPEN":
         return "🟢 Open";
+      case "CLOSED":
+        return "🟠 Sales closed";
       case "DRAWING":
         return "🔵 Drawing...";
+      case "REVEAL":
+        return "🟣 Revealing...";
       case "COMPLETED":
         return "✅ Completed";
+      case "REFUNDING":
+        return "↩️ Refunding";
     }
   };
 
@@ -1,6 +1,5 @@
 /* eslint-disable @typescript-eslint/no-unsafe-assignment */
 import {
-  Address,
   Keypair,
   nativeToScVal,
   scValToNative,
@@ -12,15 +11,30 @@ import {
   waitForTransaction,
 } from "../contracts/stellar";
 import { VRF_CONTRACT } from "../contracts/vrf";
-import { RAFFLE_CONTRACT, RAFFLE_CONTRACT_ADDRESS } from "../contracts/raffle";
+import { RAFFLE_CONTRACT } from "../contracts/raffle";
 import { defaultWallet } from "../contracts/util";
 
 /**
  * Auto-fulfill VRF for testing/demo purposes
- * In production, this would be handled by a backend oracle service
+ * In production, this would be handled by a backend oracle service.
+ * The default wallet must be the oracle configured on the VRF contract.
  */
-export const autoFulfillVRF = async (): Promise<void> => {
+export const autoFulfillVRF = async (round: number): Promise<void> => {
   try {
+    // Step 0: Look up the VRF request the round is waiting on
+    const roundInfoRaw: xdr.ScVal = await simulateReadOnly(
+      RAFFLE_CONTRACT,
+      "get_round_info",
+      ...[nativeToScVal(round, { type: "u32" })],
+    );
+    const roundInfo: { vrf_request_id?: bigint | null } =
+      scValToNative(roundInfoRaw);
+    const pendingRequestId = roundInfo.vrf_request_id;
+    if (pendingRequestId === undefined || pendingRequestId === null) {
+      throw new Error(`Round ${round} has no pending VRF request`);
+    }
+    const requestId = BigInt(pendingRequestId);
+
     // Step 1: Get random number from VRF contract
     console.log("Getting random number from VRF...");
     const randomResultRaw: xdr.ScVal = await simulateReadOnly(
@@ -28,15 +42,14 @@ export const autoFulfillVRF = async (): Promise<void> => {
       "get_random",
       ...[],
     );
-    const randomResult: number = scValToNative(randomResultRaw);
+    const randomResult: Buffer = scValToNative(randomResultRaw);
     console.log("🚀 | randomResult | randomResult:", randomResult);
 
     if (!randomResult) {
       throw new Error(`Failed to get random number`);
     }
 
-    const randomValue = BigInt(randomResult);
-    console.log("Random value:", randomValue);
+    console.log("Randomness:", randomResult.toString("hex"));
 
     // Step 2: Fulfill the VRF callback on the raffle contract
     console.log("Fulfilling VRF on raffle contract...");
@@ -47,8 +60,8 @@ export const autoFulfillVRF = async (): Promise<void> => {
       "fulfill",
       publicKeypair,
       ...[
-        new Address(RAFFLE_CONTRACT_ADDRESS).toScVal(),
-        nativeToScVal(randomValue, { type: "u64" }),
+        nativeToScVal(requestId, { type: "u64" }),
+        nativeToScVal(randomResult),
       ],
     );
 